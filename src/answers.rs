@@ -0,0 +1,246 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::Context;
+
+use crate::{run::DayReport, solution::Part};
+
+pub const DEFAULT_ANSWERS_PATH: &str = "answers.toml";
+
+/// Known-correct answers for the real puzzle inputs, keyed by day and part.
+///
+/// Stored as TOML with a table per day, e.g.
+///
+/// ```toml
+/// [day1]
+/// part1 = 1147
+/// part2 = "6789"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path).context(path.display().to_string())?;
+
+        Self::parse(&contents).context(path.display().to_string())
+    }
+
+    pub fn parse(contents: &str) -> anyhow::Result<Self> {
+        let table: toml::Table = contents.parse()?;
+
+        let mut answers = BTreeMap::new();
+
+        for (key, parts) in table {
+            let day: u8 = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or(anyhow::format_err!(
+                    "expected day table such as [day1], got [{}]",
+                    key
+                ))?;
+
+            let parts = parts
+                .as_table()
+                .ok_or(anyhow::format_err!("expected [{}] to be a table", key))?;
+
+            for (part, answer) in part_answers(parts, &key)? {
+                answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// Reads a table of `part1`/`part2` answers, where each answer is a string or integer.
+pub(crate) fn part_answers(
+    table: &toml::Table,
+    name: &str,
+) -> anyhow::Result<BTreeMap<Part, String>> {
+    table
+        .iter()
+        .map(|(key, answer)| {
+            let part: Part = key
+                .strip_prefix("part")
+                .ok_or(anyhow::format_err!(
+                    "expected part1 or part2 in {}, got {}",
+                    name,
+                    key
+                ))?
+                .parse()?;
+
+            let answer = match answer {
+                toml::Value::String(answer) => answer.clone(),
+                toml::Value::Integer(answer) => answer.to_string(),
+                _ => anyhow::bail!("expected {}.{} to be a string or integer", name, key),
+            };
+
+            Ok((part, answer))
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// No answer is stored for this part, so the computed one can't be checked.
+    Missing {
+        actual: String,
+    },
+    Error(anyhow::Error),
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub day: u8,
+    pub part: Part,
+    pub status: Status,
+}
+
+/// Checks each part of `report` against the stored answers.
+///
+/// A day that failed to parse yields an error for every one of its `parts`.
+pub fn verify(answers: &Answers, report: DayReport, parts: &[Part]) -> Vec<Verification> {
+    let day = report.day;
+
+    if let Err(error) = report.parse {
+        let message = format!("{:#}", error);
+
+        return parts
+            .iter()
+            .map(|&part| Verification {
+                day,
+                part,
+                status: Status::Error(anyhow::format_err!("{}", message)),
+            })
+            .collect();
+    }
+
+    report
+        .parts
+        .into_iter()
+        .map(|part| {
+            let status = match (part.answer, answers.get(day, part.part)) {
+                (Err(error), _) => Status::Error(error),
+                (Ok(actual), None) => Status::Missing { actual },
+                (Ok(actual), Some(expected)) if actual == expected => Status::Pass,
+                (Ok(actual), Some(expected)) => Status::Fail {
+                    expected: expected.to_string(),
+                    actual,
+                },
+            };
+
+            Verification {
+                day,
+                part: part.part,
+                status,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::run::PartReport;
+
+    fn part_report(part: Part, answer: &str) -> PartReport {
+        PartReport {
+            part,
+            answer: Ok(answer.to_string()),
+            duration: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            "[day1]
+part1 = 1147
+part2 = \"6789\"
+
+[day12]
+part1 = 3",
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Some("1147"));
+        assert_eq!(answers.get(1, Part::Two), Some("6789"));
+        assert_eq!(answers.get(12, Part::One), Some("3"));
+        assert_eq!(answers.get(12, Part::Two), None);
+        assert_eq!(answers.get(2, Part::One), None);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Answers::parse("[one]\npart1 = 1").is_err());
+        assert!(Answers::parse("[day1]\npart3 = 1").is_err());
+        assert!(Answers::parse("[day1]\npart1 = 1.5").is_err());
+        assert!(Answers::parse("day1 = 1").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let answers = Answers::parse("[day3]\npart1 = 357\npart2 = 1").unwrap();
+
+        let report = DayReport {
+            day: 3,
+            parse: Ok(Duration::ZERO),
+            parts: vec![part_report(Part::One, "357"), part_report(Part::Two, "2")],
+        };
+
+        let verifications = verify(&answers, report, &[Part::One, Part::Two]);
+
+        assert!(matches!(verifications[0].status, Status::Pass));
+        assert!(matches!(
+            &verifications[1].status,
+            Status::Fail { expected, actual } if expected == "1" && actual == "2"
+        ));
+    }
+
+    #[test]
+    fn test_verify_missing_answer() {
+        let report = DayReport {
+            day: 3,
+            parse: Ok(Duration::ZERO),
+            parts: vec![part_report(Part::One, "357")],
+        };
+
+        let verifications = verify(&Answers::default(), report, &[Part::One]);
+
+        assert!(matches!(
+            &verifications[0].status,
+            Status::Missing { actual } if actual == "357"
+        ));
+    }
+
+    #[test]
+    fn test_verify_parse_error() {
+        let report = DayReport {
+            day: 3,
+            parse: Err(anyhow::format_err!("bad input")),
+            parts: Vec::new(),
+        };
+
+        let verifications = verify(&Answers::default(), report, &[Part::One, Part::Two]);
+
+        assert_eq!(verifications.len(), 2);
+        assert!(
+            verifications
+                .iter()
+                .all(|verification| matches!(verification.status, Status::Error(_)))
+        );
+    }
+}
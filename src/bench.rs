@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use crate::{
+    run::time,
+    solution::{Part, Runner},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut samples = samples.to_vec();
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+
+        // nearest-rank percentile
+        let p95 = samples[(samples.len() * 95).div_ceil(100) - 1];
+
+        Some(Self {
+            min: samples[0],
+            median,
+            p95,
+        })
+    }
+}
+
+/// Which phase of a day a benchmark measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl std::fmt::Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Benchmark {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Runs `f` `warmup` times untimed, then `iterations` times timed.
+pub fn measure(
+    warmup: usize,
+    iterations: usize,
+    mut f: impl FnMut() -> anyhow::Result<()>,
+) -> anyhow::Result<Stats> {
+    if iterations == 0 {
+        anyhow::bail!("expected at least one iteration");
+    }
+
+    for _ in 0..warmup {
+        f()?;
+    }
+
+    let samples = (0..iterations)
+        .map(|_| {
+            let (result, duration) = time(&mut f);
+            result.map(|_| duration)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(Stats::from_samples(&samples).expect("at least one sample"))
+}
+
+/// Benchmarks parsing `input`, then each of `parts` the day has a puzzle for.
+pub fn bench_day(
+    solution: &dyn Runner,
+    input: &str,
+    parts: &[Part],
+    warmup: usize,
+    iterations: usize,
+) -> anyhow::Result<Vec<Benchmark>> {
+    let day = solution.day();
+
+    let mut benchmarks = vec![Benchmark {
+        day,
+        phase: Phase::Parse,
+        stats: measure(warmup, iterations, || solution.parse(input).map(|_| ()))?,
+    }];
+
+    let parsed = solution.parse(input)?;
+
+    for &part in solution.parts().iter().filter(|part| parts.contains(part)) {
+        benchmarks.push(Benchmark {
+            day,
+            phase: Phase::Part(part),
+            stats: measure(warmup, iterations, || parsed.solve(part).map(|_| ()))?,
+        });
+    }
+
+    Ok(benchmarks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    fn ms(milliseconds: u64) -> Duration {
+        Duration::from_millis(milliseconds)
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples: Vec<Duration> = (1..=20).rev().map(ms).collect();
+
+        assert_eq!(
+            Stats::from_samples(&samples),
+            Some(Stats {
+                min: ms(1),
+                median: Duration::from_micros(10_500),
+                p95: ms(19),
+            })
+        );
+    }
+
+    #[test]
+    fn test_stats_from_single_sample() {
+        assert_eq!(
+            Stats::from_samples(&[ms(3)]),
+            Some(Stats {
+                min: ms(3),
+                median: ms(3),
+                p95: ms(3),
+            })
+        );
+    }
+
+    #[test]
+    fn test_stats_from_no_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_measure_runs_warmup_and_iterations() {
+        let mut calls = 0;
+
+        measure(2, 5, || {
+            calls += 1;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(calls, 7);
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let benchmarks = bench_day(days::get(3).unwrap(), "9876", &[Part::Two], 1, 3).unwrap();
+
+        assert_eq!(
+            benchmarks
+                .iter()
+                .map(|benchmark| benchmark.phase)
+                .collect::<Vec<_>>(),
+            vec![Phase::Parse, Phase::Part(Part::Two)]
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use crate::grid::{Grid, Position};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A grid of on/off cells packed 64 to a word, with each row starting on a fresh word.
+///
+/// Bits past the last column of a row are always clear, so whole words can be combined without
+/// masking.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// An empty grid, with every cell off.
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(WORD_BITS);
+
+        Self {
+            width,
+            height,
+            words_per_row,
+            words: vec![0; words_per_row * height],
+        }
+    }
+
+    /// Turns on the cells of `grid` that are `on`.
+    pub fn from_grid<T>(grid: &Grid<T>, on: impl Fn(&T) -> bool) -> Self {
+        let mut bits = Self::new(grid.width(), grid.height());
+
+        for (position, cell) in grid.iter() {
+            if on(cell) {
+                bits.set(position, true);
+            }
+        }
+
+        bits
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn bit(&self, (row, column): Position) -> (usize, u64) {
+        assert!(
+            row < self.height && column < self.width,
+            "{:?} is out of bounds of a {}x{} grid",
+            (row, column),
+            self.width,
+            self.height
+        );
+
+        (
+            row * self.words_per_row + column / WORD_BITS,
+            1 << (column % WORD_BITS),
+        )
+    }
+
+    pub fn get(&self, position: Position) -> bool {
+        let (word, mask) = self.bit(position);
+
+        self.words[word] & mask != 0
+    }
+
+    pub fn set(&mut self, position: Position, on: bool) {
+        let (word, mask) = self.bit(position);
+
+        if on {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    /// Number of cells that are on.
+    pub fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Turns off every cell that is on in `other`, which must be the same size.
+    pub fn remove(&mut self, other: &BitGrid) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grids must be the same size"
+        );
+
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= !other;
+        }
+    }
+
+    /// Positions of the cells that are on, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let row = index / self.words_per_row;
+                let first_column = index % self.words_per_row * WORD_BITS;
+
+                let mut remaining = word;
+
+                std::iter::from_fn(move || {
+                    if remaining == 0 {
+                        return None;
+                    }
+
+                    let bit = remaining.trailing_zeros() as usize;
+                    remaining &= remaining - 1;
+
+                    Some((row, first_column + bit))
+                })
+            })
+    }
+
+    fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    /// The cells that are on and have fewer than `limit` of their 8 surrounding cells on.
+    ///
+    /// Neighbours are counted 64 cells at a time: each of the 8 neighbouring cells is lined up
+    /// with a shifted copy of a word, and the copies are summed into a bit-sliced counter.
+    pub fn with_fewer_neighbours_than(&self, limit: u32) -> BitGrid {
+        let mut result = BitGrid::new(self.width, self.height);
+        let empty = vec![0; self.words_per_row];
+
+        for row in 0..self.height {
+            let above = if row > 0 { self.row(row - 1) } else { &empty };
+            let current = self.row(row);
+            let below = if row + 1 < self.height {
+                self.row(row + 1)
+            } else {
+                &empty
+            };
+
+            for word in 0..self.words_per_row {
+                let mut counter = [0; 4];
+
+                for (line, include_centre) in [(above, true), (current, false), (below, true)] {
+                    let centre = line[word];
+                    let previous = if word > 0 { line[word - 1] } else { 0 };
+                    let next = line.get(word + 1).copied().unwrap_or(0);
+
+                    // bit i of each lines up the cell to the west or east of column i
+                    add(&mut counter, (centre << 1) | (previous >> (WORD_BITS - 1)));
+                    add(&mut counter, (centre >> 1) | (next << (WORD_BITS - 1)));
+
+                    if include_centre {
+                        add(&mut counter, centre);
+                    }
+                }
+
+                result.words[row * self.words_per_row + word] =
+                    current[word] & fewer_than(&counter, limit);
+            }
+        }
+
+        result
+    }
+}
+
+/// Adds one to the bit-sliced counter for every bit set in `bits`, where `counter[n]` holds bit
+/// n of each of 64 counts.
+fn add(counter: &mut [u64; 4], mut bits: u64) {
+    for slice in counter.iter_mut() {
+        let carry = *slice & bits;
+        *slice ^= bits;
+        bits = carry;
+    }
+}
+
+/// Bits whose count in the bit-sliced `counter` is below `limit`.
+fn fewer_than(counter: &[u64; 4], limit: u32) -> u64 {
+    if limit >= 1 << counter.len() {
+        return u64::MAX;
+    }
+
+    let mut less = 0;
+    let mut equal = u64::MAX;
+
+    for (bit, &slice) in counter.iter().enumerate().rev() {
+        if limit >> bit & 1 == 1 {
+            less |= equal & !slice;
+            equal &= slice;
+        } else {
+            equal &= !slice;
+        }
+    }
+
+    less
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Random;
+
+    /// A `width` by `height` grid with roughly `percent` of its cells on, the same for a given
+    /// `seed`.
+    fn generate(width: usize, height: usize, percent: u64, seed: u64) -> Grid<bool> {
+        let mut random = Random::new(seed);
+        let mut grid = Grid::new(width, height, false);
+
+        for position in grid.positions() {
+            grid[position] = random.next() % 100 < percent;
+        }
+
+        grid
+    }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut bits = BitGrid::new(70, 2);
+
+        bits.set((1, 65), true);
+        bits.set((0, 3), true);
+        bits.set((0, 3), false);
+
+        assert!(bits.get((1, 65)));
+        assert!(!bits.get((0, 3)));
+        assert_eq!(bits.count(), 1);
+        assert_eq!(bits.positions().collect::<Vec<_>>(), vec![(1, 65)]);
+    }
+
+    #[test]
+    fn test_with_fewer_neighbours_than() {
+        let grid = Grid::parse("@@@\n@@.\n..@", |c| Some(c == '@'), "a cell").unwrap();
+        let bits = BitGrid::from_grid(&grid, |&on| on);
+
+        assert_eq!(
+            bits.with_fewer_neighbours_than(3)
+                .positions()
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (2, 2)]
+        );
+        assert_eq!(bits.with_fewer_neighbours_than(0).count(), 0);
+        assert_eq!(bits.with_fewer_neighbours_than(9), bits);
+    }
+
+    #[test]
+    fn test_with_fewer_neighbours_than_matches_grid() {
+        // widths either side of a word boundary
+        for (width, seed) in [(63, 1), (64, 2), (65, 3), (200, 4)] {
+            let grid = generate(width, 50, 60, seed);
+            let bits = BitGrid::from_grid(&grid, |&on| on);
+
+            for limit in [1, 4, 8] {
+                let expected: Vec<Position> = grid
+                    .iter()
+                    .filter(|&(position, &on)| {
+                        on && grid
+                            .neighbours8(position)
+                            .filter(|&neighbour| grid[neighbour])
+                            .count()
+                            < limit as usize
+                    })
+                    .map(|(position, _)| position)
+                    .collect();
+
+                assert_eq!(
+                    bits.with_fewer_neighbours_than(limit)
+                        .positions()
+                        .collect::<Vec<_>>(),
+                    expected,
+                    "{} wide, fewer than {}",
+                    width,
+                    limit
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use crate::{
+    days,
+    input::{InputSource, input_path},
+    output::Format,
+    run::{DayReport, run_day},
+    solution::Part,
+};
+
+pub mod bench;
+pub mod fetch;
+pub mod new;
+pub mod run;
+pub mod submit;
+pub mod verify;
+pub mod watch;
+
+/// What a command works on, from the command line: the selected days and parts, where their
+/// input comes from and how answers are printed.
+pub struct Context {
+    pub days: Vec<u8>,
+    pub parts: Vec<Part>,
+    pub input: Option<InputSource>,
+    pub inputs_dir: PathBuf,
+    pub format: Format,
+}
+
+impl Context {
+    pub fn read_input(&self, day: u8) -> anyhow::Result<String> {
+        self.input
+            .clone()
+            .unwrap_or_else(|| InputSource::Path(input_path(&self.inputs_dir, day)))
+            .read()
+    }
+
+    /// Runs the selected parts of `day`, treating a missing or unreadable input like a parse
+    /// failure so the caller can report it alongside other days.
+    pub fn run_day(&self, day: u8) -> anyhow::Result<(Vec<Part>, DayReport)> {
+        let solution = days::get(day).ok_or(anyhow::format_err!(
+            "no solution registered for day {}",
+            day
+        ))?;
+
+        let parts: Vec<Part> = solution
+            .parts()
+            .iter()
+            .filter(|part| self.parts.contains(part))
+            .copied()
+            .collect();
+
+        let report = match self.read_input(day) {
+            Ok(input) => run_day(solution, &input, &parts),
+            Err(error) => DayReport {
+                day,
+                parse: Err(error),
+                parts: Vec::new(),
+            },
+        };
+
+        Ok((parts, report))
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{bench::bench_day, cli::Context, days};
+
+/// Prints a table of how long each phase of the selected days takes.
+pub fn run(context: &Context, iterations: usize, warmup: usize) -> anyhow::Result<()> {
+    println!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}",
+        "day", "phase", "min", "median", "p95"
+    );
+
+    for &day in &context.days {
+        let solution = days::get(day).ok_or(anyhow::format_err!(
+            "no solution registered for day {}",
+            day
+        ))?;
+
+        let input = context.read_input(day)?;
+
+        for benchmark in bench_day(solution, &input, &context.parts, warmup, iterations)? {
+            println!(
+                "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}",
+                benchmark.day,
+                benchmark.phase.to_string(),
+                format!("{:.2?}", benchmark.stats.min),
+                format!("{:.2?}", benchmark.stats.median),
+                format!("{:.2?}", benchmark.stats.p95),
+            );
+        }
+    }
+
+    Ok(())
+}
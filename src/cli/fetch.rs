@@ -0,0 +1,41 @@
+use crate::{
+    cli::Context,
+    client::Client,
+    fetch::{Fetched, fetch},
+    input::input_path,
+};
+
+/// Makes sure the selected days' inputs are in the inputs directory, printing where each is.
+///
+/// A client is only made from `base_url` and `session` when an input has to be downloaded, so
+/// cached inputs are listed without a session token.
+pub fn run(
+    context: &Context,
+    base_url: Option<String>,
+    session: Option<String>,
+) -> anyhow::Result<()> {
+    let missing = context
+        .days
+        .iter()
+        .any(|&day| !input_path(&context.inputs_dir, day).exists());
+
+    let client = if missing {
+        Some(Client::from_env(base_url, session)?)
+    } else {
+        None
+    };
+
+    for &day in &context.days {
+        let fetched = match &client {
+            Some(client) => fetch(client, &context.inputs_dir, day)?,
+            None => Fetched::Cached(input_path(&context.inputs_dir, day)),
+        };
+
+        match fetched {
+            Fetched::Cached(path) => println!("day {}: cached at {}", day, path.display()),
+            Fetched::Downloaded(path) => println!("day {}: downloaded to {}", day, path.display()),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use std::path::Path;
+
+use crate::scaffold::scaffold;
+
+/// Creates the files for a new day in the current directory, printing each path written.
+pub fn run(day: u8) -> anyhow::Result<()> {
+    for path in scaffold(Path::new("."), day)? {
+        println!("wrote {}", path.display());
+    }
+
+    Ok(())
+}
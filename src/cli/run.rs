@@ -0,0 +1,182 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    cli::Context,
+    days::{self, day4},
+    explain,
+    output::{Format, records},
+    pool,
+    run::{DayReport, quietly, run_day, time},
+    solution::Part,
+    visualize::{self, FrameFormat, animate, write_frames},
+};
+
+/// How `--visualize` shows a day's frames.
+pub struct Visualize {
+    pub delay: Duration,
+    pub frames: Option<PathBuf>,
+    pub format: FrameFormat,
+}
+
+/// Prints the selected days' answers: a single day's one per line, several days' as a table, or
+/// either as JSON records.
+pub fn run(context: &Context, jobs: usize) -> anyhow::Result<()> {
+    match context.format {
+        Format::Text if context.days.len() == 1 => run_text(context, context.days[0]),
+        Format::Text => run_summary(context, jobs),
+        Format::Json => run_json(context, jobs),
+    }
+}
+
+fn run_text(context: &Context, day: u8) -> anyhow::Result<()> {
+    let solution = days::get(day).ok_or(anyhow::format_err!(
+        "no solution registered for day {}",
+        day
+    ))?;
+
+    let input = context.read_input(day)?;
+
+    let report = run_day(solution, &input, &context.parts);
+
+    println!("parse: {:.2?}", report.parse?);
+
+    for part in report.parts {
+        println!(
+            "part{}: {} ({:.2?})",
+            part.part, part.answer?, part.duration
+        );
+    }
+
+    Ok(())
+}
+
+/// Runs every selected day on `jobs` threads at once, reporting on all of them.
+fn run_all(context: &Context, jobs: usize) -> anyhow::Result<Vec<(Vec<Part>, DayReport)>> {
+    // a day's panic is shown in its own row, not printed over the others as it happens
+    quietly(|| pool::map(&context.days, jobs, |&day| context.run_day(day)))
+        .into_iter()
+        .collect()
+}
+
+/// Prints a table row per phase of each day, in day order, carrying on past failing days.
+fn run_summary(context: &Context, jobs: usize) -> anyhow::Result<()> {
+    let (reports, elapsed) = time(|| run_all(context, jobs));
+
+    let mut errors = 0;
+
+    println!("{:>3}  {:<5}  {:>10}  answer", "day", "phase", "time");
+
+    for (_, report) in reports? {
+        let parse = match report.parse {
+            Ok(duration) => format!("{:.2?}", duration),
+            Err(error) => {
+                errors += 1;
+                println!(
+                    "{:>3}  {:<5}  {:>10}  ERROR {:#}",
+                    report.day, "parse", "", error
+                );
+                continue;
+            }
+        };
+
+        println!("{:>3}  {:<5}  {:>10}", report.day, "parse", parse);
+
+        for part in report.parts {
+            let answer = part.answer.unwrap_or_else(|error| {
+                errors += 1;
+                format!("ERROR {:#}", error)
+            });
+
+            println!(
+                "{:>3}  {:<5}  {:>10}  {}",
+                report.day,
+                format!("part{}", part.part),
+                format!("{:.2?}", part.duration),
+                answer
+            );
+        }
+    }
+
+    println!("\n{} days in {:.2?}", context.days.len(), elapsed);
+
+    if errors > 0 {
+        anyhow::bail!("{} phases failed", errors);
+    }
+
+    Ok(())
+}
+
+/// Prints a JSON record per part, carrying on past failing days so every record is emitted.
+fn run_json(context: &Context, jobs: usize) -> anyhow::Result<()> {
+    let mut errors = 0;
+
+    for (parts, report) in run_all(context, jobs)? {
+        for record in records(&report, &parts) {
+            if record.error.is_some() {
+                errors += 1;
+            }
+
+            println!("{}", record.to_json());
+        }
+    }
+
+    if errors > 0 {
+        anyhow::bail!("{} parts failed", errors);
+    }
+
+    Ok(())
+}
+
+/// Animates the selected day in the terminal, also writing its frames to disk if asked to.
+pub fn visualize(
+    context: &Context,
+    visualize: &Visualize,
+    rule: Option<&day4::Rule>,
+) -> anyhow::Result<()> {
+    let day = context.days[0];
+
+    let visualization = visualize::frames(day, &context.read_input(day)?, rule)?;
+
+    animate(
+        &mut std::io::stdout().lock(),
+        &visualization.frames,
+        visualize.delay,
+    )?;
+
+    if let Some(dir) = &visualize.frames {
+        let paths = write_frames(dir, &visualization, visualize.format)?;
+
+        println!("wrote {} frames to {}", paths.len(), dir.display());
+    }
+
+    Ok(())
+}
+
+/// Prints day 4's answers with rolls accessible by `rule` instead of the puzzle's rule.
+pub fn with_rule(context: &Context, rule: &day4::Rule) -> anyhow::Result<()> {
+    let grid = day4::parse(&context.read_input(4)?)?;
+
+    for &part in &context.parts {
+        println!("part{}: {}", part, day4::answer(&grid, rule, part));
+    }
+
+    Ok(())
+}
+
+/// Prints how the selected day's answers are reached.
+pub fn explain(context: &Context) -> anyhow::Result<()> {
+    let day = context.days[0];
+    let input = context.read_input(day)?;
+
+    for &part in &context.parts {
+        let explanation = explain::explain(day, &input, part)?;
+
+        println!("part{}:", part);
+
+        for line in explanation {
+            println!("  {}", line);
+        }
+    }
+
+    Ok(())
+}
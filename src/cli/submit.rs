@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use crate::{
+    cli::Context,
+    client::Client,
+    submit::{Guesses, Verdict, submit},
+};
+
+/// Submits `answer`, or the one computed from the day's input, for the selected day and part,
+/// recording it in `guesses`.
+pub fn run(
+    context: &Context,
+    client: &Client,
+    answer: Option<&str>,
+    guesses: &Path,
+) -> anyhow::Result<()> {
+    let day = context.days[0];
+    let part = context.parts[0];
+
+    let answer = match answer {
+        Some(answer) => answer.to_string(),
+        None => {
+            let (_, report) = context.run_day(day)?;
+            report.parse?;
+
+            let part = report.parts.into_iter().next().ok_or(anyhow::format_err!(
+                "day {} has no part {}",
+                day,
+                part
+            ))?;
+
+            part.answer?
+        }
+    };
+
+    let mut guesses = Guesses::load(guesses)?;
+
+    let response = submit(client, &mut guesses, day, part, &answer)?;
+
+    match response.wait {
+        Some(wait) => println!(
+            "day {} part {}: {} is {}; wait {:?} before trying again",
+            day, part, answer, response.verdict, wait
+        ),
+        None => println!(
+            "day {} part {}: {} is {}",
+            day, part, answer, response.verdict
+        ),
+    }
+
+    if response.verdict != Verdict::Correct {
+        anyhow::bail!("{} was not accepted", answer);
+    }
+
+    Ok(())
+}
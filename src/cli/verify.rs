@@ -0,0 +1,52 @@
+use std::path::Path;
+
+use crate::{
+    answers::{Answers, Status, verify},
+    cli::Context,
+};
+
+/// Checks the selected days' answers against those known in `answers`, printing each part's
+/// outcome.
+pub fn run(context: &Context, answers: &Path) -> anyhow::Result<()> {
+    let answers = Answers::load(answers)?;
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for &day in &context.days {
+        let (parts, report) = context.run_day(day)?;
+
+        for verification in verify(&answers, report, &parts) {
+            let label = format!("day {} part {}", verification.day, verification.part);
+
+            match verification.status {
+                Status::Pass => {
+                    passed += 1;
+                    println!("{}: pass", label);
+                }
+                Status::Fail { expected, actual } => {
+                    failed += 1;
+                    println!("{}: FAIL expected {}, got {}", label, expected, actual);
+                }
+                Status::Missing { actual } => {
+                    missing += 1;
+                    println!("{}: missing answer, got {}", label, actual);
+                }
+                Status::Error(error) => {
+                    failed += 1;
+                    println!("{}: ERROR {:#}", label, error);
+                }
+            }
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        passed, failed, missing
+    );
+
+    if failed > 0 {
+        anyhow::bail!("{} answers did not verify", failed);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use std::{path::Path, time::Duration};
+
+use crate::{
+    cli::Context,
+    days,
+    fixtures::EXAMPLES_DIR,
+    input::{InputSource, input_path},
+    watch::{self, Change, diff},
+};
+
+/// Re-runs the selected day and its examples whenever their files change, printing how each
+/// result compares with the previous run.
+pub fn run(context: &Context, interval: Duration) -> anyhow::Result<()> {
+    let day = context.days[0];
+    let solution = days::get(day).ok_or(anyhow::format_err!(
+        "no solution registered for day {}",
+        day
+    ))?;
+
+    let input = match &context.input {
+        Some(InputSource::Path(path)) => Some(path.clone()),
+        Some(_) => None,
+        None => Some(input_path(&context.inputs_dir, day)),
+    };
+    let examples = Path::new(EXAMPLES_DIR).join(format!("day{}", day));
+
+    let mut previous = watch::Results::new();
+    let mut runs = 0;
+
+    watch::watch(
+        || watch::watched_files(input.as_deref(), &examples),
+        interval,
+        || {
+            runs += 1;
+
+            let current =
+                watch::results(solution, context.read_input(day), &context.parts, &examples);
+            // the first run has nothing to compare with
+            let changes = diff(if runs == 1 { &current } else { &previous }, &current);
+
+            let changed = changes
+                .iter()
+                .filter(|(_, change)| !matches!(change, Change::Unchanged(_)))
+                .count();
+
+            if runs == 1 {
+                println!("day {} run {}", day, runs);
+            } else {
+                println!("day {} run {}: {} changed", day, runs, changed);
+            }
+
+            for (label, change) in changes {
+                println!("  {}: {}", label, change);
+            }
+
+            previous = current;
+        },
+    )
+}
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use anyhow::Context;
+
+use crate::solution::Part;
+
+/// Environment variable overriding the Advent of Code server, e.g. to point at a local stub.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// Environment variable holding the `session` cookie of a logged in Advent of Code account.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub const YEAR: u16 = 2025;
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Talks to the Advent of Code server as the account owning `session`.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent,
+        }
+    }
+
+    /// Uses `base_url` and `session` when given, falling back to `AOC_BASE_URL` and `AOC_SESSION`.
+    pub fn from_env(base_url: Option<String>, session: Option<String>) -> anyhow::Result<Self> {
+        let base_url = base_url
+            .or_else(|| std::env::var(BASE_URL_ENV).ok())
+            .unwrap_or(DEFAULT_BASE_URL.to_string());
+
+        let Some(session) = session.or_else(|| std::env::var(SESSION_ENV).ok()) else {
+            anyhow::bail!(
+                "expected a session token from --session or ${}",
+                SESSION_ENV
+            );
+        };
+
+        Ok(Self::new(&base_url, session.trim()))
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Downloads a day's puzzle input.
+    pub fn input(&self, day: u8) -> anyhow::Result<String> {
+        let url = format!("{}/input", self.day_url(day));
+
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", self.cookie())
+            .call()
+            .context(url.clone())?;
+
+        read_body(&url, day, response)
+    }
+
+    /// Posts an answer for one part of a day, returning the page the server responds with.
+    pub fn answer(&self, day: u8, part: Part, answer: &str) -> anyhow::Result<String> {
+        let url = format!("{}/answer", self.day_url(day));
+
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])
+            .context(url.clone())?;
+
+        read_body(&url, day, response)
+    }
+}
+
+fn read_body(
+    url: &str,
+    day: u8,
+    mut response: ureq::http::Response<ureq::Body>,
+) -> anyhow::Result<String> {
+    let status = response.status().as_u16();
+    let body = response
+        .body_mut()
+        .read_to_string()
+        .context(url.to_string())?;
+
+    match status {
+        200 => Ok(body),
+        400 | 401 | 403 => anyhow::bail!("{}: session token was rejected ({})", url, status),
+        404 => anyhow::bail!("{}: day {} is not available yet", url, day),
+        _ => anyhow::bail!("{}: unexpected status {}: {}", url, status, body.trim()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_url() {
+        let client = Client::new("http://localhost:8080/", "abc");
+
+        assert_eq!(client.base_url(), "http://localhost:8080");
+        assert_eq!(client.day_url(4), "http://localhost:8080/2025/day/4");
+        assert_eq!(client.cookie(), "session=abc");
+    }
+}
@@ -1,4 +1,9 @@
+use crate::solution::Runner;
+
 pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
 pub mod day2;
 pub mod day3;
 pub mod day4;
@@ -7,6 +12,52 @@ pub mod day6;
 pub mod day7;
 pub mod day8;
 pub mod day9;
-pub mod day10;
-pub mod day11;
-pub mod day12;
+
+pub static SOLUTIONS: &[&dyn Runner] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+];
+
+pub fn get(day: u8) -> Option<&'static dyn Runner> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.day() == day)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_ordered_by_day() {
+        let days: Vec<u8> = SOLUTIONS.iter().map(|solution| solution.day()).collect();
+
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", days);
+        assert!(days.iter().all(|day| (1..=25).contains(day)), "{:?}", days);
+    }
+
+    #[test]
+    fn test_get() {
+        for solution in SOLUTIONS {
+            assert_eq!(
+                get(solution.day()).map(|solution| solution.day()),
+                Some(solution.day())
+            );
+        }
+
+        // outside of Advent of Code, so never registered
+        assert!(get(0).is_none());
+        assert!(get(26).is_none());
+    }
+}
@@ -1,19 +1,24 @@
 use std::{fmt::Display, str::FromStr};
 
-#[derive(PartialEq)]
-enum Direction {
+use crate::{
+    parse::{Line, ParseError, lines},
+    solution::Solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
     Left,
     Right,
 }
 
 impl FromStr for Direction {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         match value {
             "L" => Ok(Direction::Left),
             "R" => Ok(Direction::Right),
-            _ => anyhow::bail!("expected direction of L or R"),
+            _ => Err(ParseError::new(1, 1, value, "expected direction of L or R")),
         }
     }
 }
@@ -27,18 +32,27 @@ impl Display for Direction {
     }
 }
 
-struct Rotation {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation {
     direction: Direction,
     distance: u16,
 }
 
-impl FromStr for Rotation {
-    type Err = anyhow::Error;
+impl Rotation {
+    pub fn new(direction: Direction, distance: u16) -> Self {
+        Self {
+            direction,
+            distance,
+        }
+    }
 
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let direction: Direction = value[..1].parse()?;
+    fn from_line(line: Line<'_>) -> Result<Self, ParseError> {
+        let split = line.text.chars().next().map_or(0, char::len_utf8);
+        let (direction, distance) = line.text.split_at(split);
 
-        let distance: u16 = value[1..].parse()?;
+        let direction: Direction = line.parse(direction, "direction of L or R")?;
+
+        let distance: u16 = line.parse(distance, "distance")?;
 
         Ok(Self {
             distance,
@@ -47,17 +61,30 @@ impl FromStr for Rotation {
     }
 }
 
+impl FromStr for Rotation {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::from_line(Line::new(1, value))
+    }
+}
+
 impl Display for Rotation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}{}", self.direction, self.distance)
     }
 }
 
-struct Dial {
+#[derive(Debug)]
+pub struct Dial {
     clicks: u8,
 }
 
 impl Dial {
+    pub fn clicks(&self) -> u8 {
+        self.clicks
+    }
+
     pub fn rotate(&mut self, rotation: &Rotation) {
         let distance: u8 = (rotation.distance % 100) as u8;
 
@@ -144,21 +171,35 @@ impl Dial {
     }
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let rotations = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(str::parse::<Rotation>)
-        .collect::<anyhow::Result<Vec<_>>>()?;
+impl Default for Dial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input<'a> = Vec<Rotation>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    println!(
-        "part 1: {}",
-        Dial::new().count_zeroes_between_rotations(rotations.iter())
-    );
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(lines(input)
+            .filter(|line| !line.text.is_empty())
+            .map(Rotation::from_line)
+            .collect::<Result<_, _>>()?)
+    }
 
-    println!("part 2: {}", Dial::new().count_zeroes(rotations.iter()));
+    fn part1(rotations: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        Ok(Dial::new().count_zeroes_between_rotations(rotations.iter()))
+    }
 
-    Ok(())
+    fn part2(rotations: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        Ok(Dial::new().count_zeroes(rotations.iter()))
+    }
 }
 
 #[cfg(test)]
@@ -169,21 +210,11 @@ mod tests {
     fn test_count_zeroes_between_rotations() {
         let mut dial = Dial::new();
 
-        let rotations = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82"
-        .to_string()
-        .lines()
-        .map(str::parse::<Rotation>)
-        .collect::<anyhow::Result<Vec<_>>>()
-        .unwrap();
+        let rotations = include_str!("../../../examples/day1/example.txt")
+            .lines()
+            .map(str::parse::<Rotation>)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
 
         assert_eq!(dial.count_zeroes_between_rotations(rotations.iter()), 3);
     }
@@ -191,21 +222,11 @@ L82"
     fn test_count_zeroes() {
         let mut dial = Dial::new();
 
-        let rotations = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82"
-        .to_string()
-        .lines()
-        .map(str::parse::<Rotation>)
-        .collect::<anyhow::Result<Vec<_>>>()
-        .unwrap();
+        let rotations = include_str!("../../../examples/day1/example.txt")
+            .lines()
+            .map(str::parse::<Rotation>)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
 
         assert_eq!(dial.count_zeroes(rotations.iter()), 6);
     }
@@ -310,21 +331,11 @@ L82"
 
         let mut clicks: Vec<u8> = Vec::new();
 
-        let rotations = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82"
-        .to_string()
-        .lines()
-        .map(str::parse::<Rotation>)
-        .collect::<anyhow::Result<Vec<_>>>()
-        .unwrap();
+        let rotations = include_str!("../../../examples/day1/example.txt")
+            .lines()
+            .map(str::parse::<Rotation>)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
 
         for rotation in rotations {
             dial.rotate(&rotation);
@@ -334,6 +345,23 @@ L82"
         assert_eq!(clicks, vec![82, 52, 0, 95, 55, 0, 99, 0, 14, 32]);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Day1::parse("L68\nX30").unwrap_err().to_string(),
+            "line 2, column 1: expected direction of L or R, found \"X\""
+        );
+        assert_eq!(
+            Day1::parse("L68\nR3a0").unwrap_err().to_string(),
+            "line 2, column 2: expected distance, found \"3a0\""
+        );
+        assert_eq!(
+            Day1::parse("L68\nR").unwrap_err().to_string(),
+            "line 2, column 2: expected distance, found end of line"
+        );
+        assert!(Day1::parse("é1").is_err());
+    }
+
     #[test]
     fn test_overflowing_rotations() {
         let mut dial = Dial::new();
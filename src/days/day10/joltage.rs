@@ -0,0 +1,353 @@
+//! Configuring the joltage level counters as an integer linear program.
+//!
+//! Pressing button `j` `x[j]` times raises each counter it's wired to by `x[j]`, so the presses
+//! solve `A·x = target` where `A[i][j]` is how many times button `j` lists counter `i`. Of the
+//! non-negative integer solutions, we want the one with the fewest presses in total.
+
+/// An equation left after elimination: `pivot·x[column] = rhs - Σ coefficients[k]·free[k]`,
+/// over the free buttons `free[k]`.
+#[derive(Debug, Clone)]
+struct Row {
+    column: usize,
+    pivot: i64,
+    coefficients: Vec<i64>,
+    rhs: i64,
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+/// Brings the augmented matrix `[A | target]` to reduced row echelon form using only integer
+/// row operations, so nothing is lost to rounding. Returns the pivot rows, and the columns
+/// without a pivot, or `None` when a row reduces to `0 = n` for a non-zero `n`.
+fn eliminate(target: &[usize], buttons: &[Vec<usize>]) -> Option<(Vec<Row>, Vec<usize>)> {
+    let columns = buttons.len();
+
+    let mut matrix: Vec<Vec<i64>> = target
+        .iter()
+        .enumerate()
+        .map(|(counter, &level)| {
+            buttons
+                .iter()
+                .map(|button| button.iter().filter(|&&wired| wired == counter).count() as i64)
+                .chain([level as i64])
+                .collect()
+        })
+        .collect();
+
+    let mut pivots = Vec::new();
+
+    for column in 0..columns {
+        let rank = pivots.len();
+
+        let Some(pivot) = (rank..matrix.len()).find(|&row| matrix[row][column] != 0) else {
+            continue;
+        };
+
+        matrix.swap(rank, pivot);
+        let pivot_row = matrix[rank].clone();
+
+        for (index, row) in matrix.iter_mut().enumerate() {
+            let factor = row[column];
+
+            if index == rank || factor == 0 {
+                continue;
+            }
+
+            for (value, &pivot_value) in row.iter_mut().zip(&pivot_row) {
+                *value = *value * pivot_row[column] - pivot_value * factor;
+            }
+
+            // keep the entries small by dividing out their common factor
+            let divisor = row.iter().fold(0, |divisor, &value| gcd(divisor, value));
+            if divisor > 1 {
+                row.iter_mut().for_each(|value| *value /= divisor);
+            }
+        }
+
+        pivots.push(column);
+    }
+
+    if matrix[pivots.len()..].iter().any(|row| row[columns] != 0) {
+        return None;
+    }
+
+    let free: Vec<usize> = (0..columns)
+        .filter(|column| !pivots.contains(column))
+        .collect();
+
+    let rows = pivots
+        .iter()
+        .zip(&matrix)
+        .map(|(&column, row)| {
+            // make the pivot positive, so a non-negative press count needs a non-negative rhs
+            let sign = row[column].signum();
+
+            Row {
+                column,
+                pivot: row[column] * sign,
+                coefficients: free.iter().map(|&free| row[free] * sign).collect(),
+                rhs: row[columns] * sign,
+            }
+        })
+        .collect();
+
+    Some((rows, free))
+}
+
+/// Branch and bound over the presses of the free buttons, from which every other button's
+/// presses follow.
+struct Search<'a> {
+    rows: &'a [Row],
+    /// Most presses each free button can take before overshooting a counter.
+    limits: Vec<i64>,
+    /// How much one press of each free button changes the total presses, once the pivot
+    /// buttons make up the difference.
+    weights: Vec<f64>,
+    fewest: Option<(i64, Vec<i64>)>,
+}
+
+impl Search<'_> {
+    /// Tries every number of presses of the free buttons from `next` on, given `remaining`, each
+    /// row's rhs less the presses chosen so far, and `pressed`, the free presses chosen so far.
+    fn search(&mut self, next: usize, remaining: &[i64], pressed: &mut Vec<i64>) {
+        let undecided = next..self.limits.len();
+
+        // every pivot button must be pressed a non-negative number of times, however the
+        // undecided free buttons are pressed
+        for (row, &remaining) in self.rows.iter().zip(remaining) {
+            let most = remaining
+                + undecided
+                    .clone()
+                    .map(|free| (-row.coefficients[free]).max(0) * self.limits[free])
+                    .sum::<i64>();
+
+            if most < 0 {
+                return;
+            }
+        }
+
+        if let Some((fewest, _)) = &self.fewest {
+            let bound = pressed.iter().sum::<i64>() as f64
+                + self
+                    .rows
+                    .iter()
+                    .zip(remaining)
+                    .map(|(row, &remaining)| remaining as f64 / row.pivot as f64)
+                    .sum::<f64>()
+                + undecided
+                    .clone()
+                    .map(|free| (self.weights[free] * self.limits[free] as f64).min(0.0))
+                    .sum::<f64>();
+
+            // totals are whole, so only a bound at least one below the best can improve on it
+            if bound > (*fewest - 1) as f64 + 1e-6 {
+                return;
+            }
+        }
+
+        if next == self.limits.len() {
+            self.evaluate(remaining, pressed);
+            return;
+        }
+
+        for presses in 0..=self.limits[next] {
+            let remaining: Vec<i64> = self
+                .rows
+                .iter()
+                .zip(remaining)
+                .map(|(row, &remaining)| remaining - row.coefficients[next] * presses)
+                .collect();
+
+            pressed.push(presses);
+            self.search(next + 1, &remaining, pressed);
+            pressed.pop();
+        }
+    }
+
+    fn evaluate(&mut self, remaining: &[i64], pressed: &[i64]) {
+        let mut total: i64 = pressed.iter().sum();
+        let mut pivots = Vec::with_capacity(self.rows.len());
+
+        for (row, &remaining) in self.rows.iter().zip(remaining) {
+            if remaining < 0 || remaining % row.pivot != 0 {
+                return;
+            }
+
+            pivots.push(remaining / row.pivot);
+            total += remaining / row.pivot;
+        }
+
+        if self
+            .fewest
+            .as_ref()
+            .is_none_or(|(fewest, _)| total < *fewest)
+        {
+            self.fewest = Some((total, [pressed, &pivots].concat()));
+        }
+    }
+}
+
+/// How many times to press each button, fewest in total, to raise the counters from zero to
+/// exactly `target`, or `None` if no presses do.
+pub fn min_presses(target: &[usize], buttons: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let (rows, free) = eliminate(target, buttons)?;
+
+    let limits = free
+        .iter()
+        .map(|&button| {
+            buttons[button]
+                .iter()
+                .map(|&counter| {
+                    let wired = buttons[button].iter().filter(|&&c| c == counter).count();
+                    (target[counter] / wired) as i64
+                })
+                .min()
+                // a button wired to nothing does nothing, so is never worth pressing
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let weights = (0..free.len())
+        .map(|free| {
+            1.0 - rows
+                .iter()
+                .map(|row| row.coefficients[free] as f64 / row.pivot as f64)
+                .sum::<f64>()
+        })
+        .collect();
+
+    let mut search = Search {
+        rows: &rows,
+        limits,
+        weights,
+        fewest: None,
+    };
+
+    let remaining: Vec<i64> = rows.iter().map(|row| row.rhs).collect();
+    search.search(0, &remaining, &mut Vec::new());
+
+    let (_, found) = search.fewest?;
+
+    let mut presses = vec![0; buttons.len()];
+    let columns = free.iter().chain(rows.iter().map(|row| &row.column));
+
+    for (&column, count) in columns.zip(found) {
+        presses[column] = count as usize;
+    }
+
+    Some(presses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(presses: &[usize], buttons: &[Vec<usize>], len: usize) -> Vec<usize> {
+        let mut levels = vec![0; len];
+
+        for (&count, button) in presses.iter().zip(buttons) {
+            for &counter in button {
+                levels[counter] += count;
+            }
+        }
+
+        levels
+    }
+
+    #[test]
+    fn test_min_presses() {
+        for (target, buttons, fewest) in [
+            (
+                vec![3, 5, 4, 7],
+                vec![
+                    vec![3],
+                    vec![1, 3],
+                    vec![2],
+                    vec![2, 3],
+                    vec![0, 2],
+                    vec![0, 1],
+                ],
+                10,
+            ),
+            (
+                vec![7, 5, 12, 7, 2],
+                vec![
+                    vec![0, 2, 3, 4],
+                    vec![2, 3],
+                    vec![0, 4],
+                    vec![0, 1, 2],
+                    vec![1, 2, 3, 4],
+                ],
+                12,
+            ),
+            (
+                vec![10, 11, 11, 5, 10, 5],
+                vec![
+                    vec![0, 1, 2, 3, 4],
+                    vec![0, 3, 4],
+                    vec![0, 1, 2, 4, 5],
+                    vec![1, 2],
+                ],
+                11,
+            ),
+        ] {
+            let presses = min_presses(&target, &buttons).unwrap();
+
+            assert_eq!(presses.iter().sum::<usize>(), fewest);
+            assert_eq!(levels(&presses, &buttons, target.len()), target);
+        }
+    }
+
+    #[test]
+    fn test_min_presses_unreachable() {
+        // both counters always rise together
+        assert_eq!(min_presses(&[1, 2], &[vec![0, 1]]), None);
+        // solvable only with a negative number of presses
+        assert_eq!(
+            min_presses(&[1, 0, 1], &[vec![0, 1], vec![1, 2], vec![1]]),
+            None
+        );
+        assert_eq!(min_presses(&[1], &[]), None);
+    }
+
+    #[test]
+    fn test_min_presses_matches_exhaustive_search() {
+        let buttons = vec![
+            vec![0, 1],
+            vec![1, 2],
+            vec![0, 2],
+            vec![0, 1, 2],
+            vec![2],
+            vec![1],
+        ];
+
+        let targets = [[4, 6, 5], [0, 3, 3], [9, 2, 7], [5, 5, 5]];
+        let mut fewest = [None; 4];
+
+        // every way of pressing each button up to 9 times
+        for code in 0..10usize.pow(buttons.len() as u32) {
+            let presses: Vec<usize> = (0..buttons.len())
+                .map(|button| code / 10usize.pow(button as u32) % 10)
+                .collect();
+            let levels = levels(&presses, &buttons, 3);
+
+            for (target, fewest) in targets.iter().zip(&mut fewest) {
+                if levels == target {
+                    let total = presses.iter().sum::<usize>();
+                    *fewest = Some(fewest.map_or(total, |fewest: usize| fewest.min(total)));
+                }
+            }
+        }
+
+        for (target, fewest) in targets.iter().zip(fewest) {
+            assert_eq!(
+                min_presses(target, &buttons).map(|presses| presses.iter().sum::<usize>()),
+                fewest,
+                "{:?}",
+                target
+            );
+        }
+    }
+}
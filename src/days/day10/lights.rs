@@ -0,0 +1,393 @@
+//! Configuring the indicator lights as a linear system over GF(2).
+//!
+//! Pressing a button twice undoes it, so each button is either pressed once or not at all, and
+//! a light ends up on when an odd number of the pressed buttons toggle it. That's one equation
+//! per light in one unknown per button, with addition being XOR.
+
+use std::{collections::VecDeque, fmt::Display};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A vector over GF(2), packed 64 entries to a word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bits(Vec<u64>);
+
+impl Bits {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(WORD_BITS)])
+    }
+
+    fn get(&self, index: usize) -> bool {
+        self.0[index / WORD_BITS] >> (index % WORD_BITS) & 1 == 1
+    }
+
+    fn flip(&mut self, index: usize) {
+        self.0[index / WORD_BITS] ^= 1 << (index % WORD_BITS);
+    }
+
+    fn add(&mut self, other: &Bits) {
+        for (word, other) in self.0.iter_mut().zip(&other.0) {
+            *word ^= other;
+        }
+    }
+
+    fn count(&self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+}
+
+/// Most steps a search for the fewest presses may take, each adding one vector to another.
+const SEARCH_LIMIT: u64 = 1 << 26;
+/// Most combinations of lights the breadth-first search may keep track of.
+const STATES_LIMIT: u64 = 1 << 22;
+
+/// `2^bits`, or `u64::MAX` if that doesn't fit.
+fn power_of_two(bits: usize) -> u64 {
+    u32::try_from(bits)
+        .ok()
+        .and_then(|bits| 1u64.checked_shl(bits))
+        .unwrap_or(u64::MAX)
+}
+
+/// The fewest presses can't be found in reasonable time, as there are too many buttons both
+/// independent of each other and redundant with them. Either is searched when there are at most
+/// `max_independent` independent buttons or `max_redundant` redundant ones, so 40 lights and 80
+/// buttons, say, are out of reach.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchTooLarge {
+    pub independent: usize,
+    pub redundant: usize,
+    pub max_independent: usize,
+    pub max_redundant: usize,
+}
+
+impl Display for SearchTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "too many buttons to search, with {} independent and {} redundant, \
+             where at most {} independent or {} redundant can be searched",
+            self.independent, self.redundant, self.max_independent, self.max_redundant
+        )
+    }
+}
+
+impl std::error::Error for SearchTooLarge {}
+
+/// The equations `A·x = target` in reduced row echelon form, one per light, along with the
+/// column of each row's leading entry.
+struct Reduced {
+    rows: Vec<(Bits, bool)>,
+    pivots: Vec<usize>,
+    columns: usize,
+}
+
+impl Reduced {
+    /// Reduces the equations, or `None` when a row reduces to `0 = 1`.
+    fn new(target: &[bool], buttons: &[Vec<usize>]) -> Option<Self> {
+        let columns = buttons.len();
+
+        let mut rows: Vec<(Bits, bool)> = target
+            .iter()
+            .enumerate()
+            .map(|(light, &on)| {
+                let mut coefficients = Bits::new(columns);
+
+                for (button, lights) in buttons.iter().enumerate() {
+                    // a button listing a light twice toggles it twice
+                    if lights.iter().filter(|&&toggled| toggled == light).count() % 2 == 1 {
+                        coefficients.flip(button);
+                    }
+                }
+
+                (coefficients, on)
+            })
+            .collect();
+
+        let mut pivots = Vec::new();
+
+        for column in 0..columns {
+            let rank = pivots.len();
+
+            let Some(pivot) = (rank..rows.len()).find(|&row| rows[row].0.get(column)) else {
+                continue;
+            };
+
+            rows.swap(rank, pivot);
+            let (pivot_row, pivot_on) = rows[rank].clone();
+
+            for (row, (coefficients, on)) in rows.iter_mut().enumerate() {
+                if row != rank && coefficients.get(column) {
+                    coefficients.add(&pivot_row);
+                    *on ^= pivot_on;
+                }
+            }
+
+            pivots.push(column);
+        }
+
+        // rows without a pivot have no buttons left, so can't turn a light on
+        if rows[pivots.len()..].iter().any(|&(_, on)| on) {
+            return None;
+        }
+
+        Some(Self {
+            rows,
+            pivots,
+            columns,
+        })
+    }
+
+    fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    /// Finds one solution along with a basis of the null space, the sets of buttons whose
+    /// presses cancel out. Every solution is the first plus some combination of the basis, so
+    /// the combinations are walked in Gray code order, changing one basis vector at a time, to
+    /// find the one pressing fewest buttons. Takes `2^(columns - rank)` steps.
+    fn walk_null_space(&self) -> Vec<bool> {
+        let mut presses = Bits::new(self.columns);
+
+        for (row, &column) in self.pivots.iter().enumerate() {
+            if self.rows[row].1 {
+                presses.flip(column);
+            }
+        }
+
+        let basis: Vec<Bits> = (0..self.columns)
+            .filter(|column| !self.pivots.contains(column))
+            .map(|free| {
+                let mut vector = Bits::new(self.columns);
+                vector.flip(free);
+
+                for (row, &column) in self.pivots.iter().enumerate() {
+                    if self.rows[row].0.get(free) {
+                        vector.flip(column);
+                    }
+                }
+
+                vector
+            })
+            .collect();
+
+        let mut fewest = presses.clone();
+
+        for step in 1..1u64 << basis.len() {
+            presses.add(&basis[step.trailing_zeros() as usize]);
+
+            if presses.count() < fewest.count() {
+                fewest = presses.clone();
+            }
+        }
+
+        (0..self.columns).map(|button| fewest.get(button)).collect()
+    }
+
+    /// Searches breadth first over which of the reduced equations are satisfied, each button
+    /// flipping some of them, so the first time every equation is satisfied is with the fewest
+    /// presses. Takes `2^rank` steps for each button.
+    fn breadth_first(&self) -> Vec<bool> {
+        let flips: Vec<usize> = (0..self.columns)
+            .map(|button| {
+                (0..self.rank())
+                    .filter(|&row| self.rows[row].0.get(button))
+                    .fold(0, |flips, row| flips | 1 << row)
+            })
+            .collect();
+
+        let target = (0..self.rank())
+            .filter(|&row| self.rows[row].1)
+            .fold(0, |target, row| target | 1 << row);
+
+        // the button last pressed to reach each state, or UNSEEN
+        const UNSEEN: u32 = u32::MAX;
+        let mut via = vec![UNSEEN; 1 << self.rank()];
+        let mut queue = VecDeque::from([0]);
+
+        while let Some(state) = queue.pop_front()
+            && state != target
+        {
+            for (button, &flips) in flips.iter().enumerate() {
+                let next = state ^ flips;
+
+                if next != 0 && via[next] == UNSEEN {
+                    via[next] = button as u32;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        // each pivot's button flips just its own equation, so every state is reachable, and a
+        // shortest path never presses a button twice, as the two presses would cancel out
+        let mut presses = vec![false; self.columns];
+        let mut state = target;
+
+        while state != 0 {
+            let button = via[state] as usize;
+            presses[button] = true;
+            state ^= flips[button];
+        }
+
+        presses
+    }
+}
+
+/// Which buttons to press, fewest in total, to turn the lights from all off to `target`, or
+/// `None` if no combination of buttons does.
+///
+/// After elimination, the fewest presses are found by walking the null space when few buttons
+/// are redundant, or by searching over the equations when there are few of them, whichever is
+/// quicker. When both would take too long, that's an error instead: only a couple of dozen
+/// buttons of either kind can be searched.
+pub fn min_presses(
+    target: &[bool],
+    buttons: &[Vec<usize>],
+) -> Result<Option<Vec<bool>>, SearchTooLarge> {
+    let Some(reduced) = Reduced::new(target, buttons) else {
+        return Ok(None);
+    };
+
+    let (independent, redundant) = (reduced.rank(), reduced.columns - reduced.rank());
+
+    let walk = power_of_two(redundant);
+    let states = power_of_two(independent);
+    let breadth = states.saturating_mul(reduced.columns as u64);
+
+    let can_walk = walk <= SEARCH_LIMIT;
+    let can_search = states <= STATES_LIMIT && breadth <= SEARCH_LIMIT;
+
+    if can_walk && (!can_search || walk <= breadth) {
+        Ok(Some(reduced.walk_null_space()))
+    } else if can_search {
+        Ok(Some(reduced.breadth_first()))
+    } else {
+        Err(SearchTooLarge {
+            independent,
+            redundant,
+            max_independent: STATES_LIMIT
+                .min(SEARCH_LIMIT / reduced.columns as u64)
+                .ilog2() as usize,
+            max_redundant: SEARCH_LIMIT.ilog2() as usize,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Random;
+
+    fn lights(presses: &[bool], buttons: &[Vec<usize>], len: usize) -> Vec<bool> {
+        let mut lights = vec![false; len];
+
+        for (_, button) in presses.iter().zip(buttons).filter(|(pressed, _)| **pressed) {
+            for &light in button {
+                lights[light] = !lights[light];
+            }
+        }
+
+        lights
+    }
+
+    #[test]
+    fn test_min_presses() {
+        let buttons = vec![
+            vec![3],
+            vec![1, 3],
+            vec![2],
+            vec![2, 3],
+            vec![0, 2],
+            vec![0, 1],
+        ];
+
+        let target = [false, true, true, false];
+        let presses = min_presses(&target, &buttons).unwrap().unwrap();
+
+        assert_eq!(presses.iter().filter(|&&pressed| pressed).count(), 2);
+        assert_eq!(lights(&presses, &buttons, 4), target);
+        assert_eq!(min_presses(&[false; 4], &buttons), Ok(Some(vec![false; 6])));
+    }
+
+    #[test]
+    fn test_min_presses_unreachable() {
+        assert_eq!(min_presses(&[true, false], &[vec![0, 1]]), Ok(None));
+        assert_eq!(min_presses(&[true], &[vec![0, 0]]), Ok(None));
+    }
+
+    /// A machine with `count` random buttons over `len` lights, and a target reachable by
+    /// pressing about a third of them.
+    fn random_machine(len: usize, count: usize, seed: u64) -> (Vec<Vec<usize>>, Vec<bool>) {
+        let mut random = Random::new(seed);
+
+        let buttons: Vec<Vec<usize>> = (0..count)
+            .map(|_| {
+                (0..len)
+                    .filter(|_| random.next().is_multiple_of(4))
+                    .collect()
+            })
+            .collect();
+        let pressed: Vec<bool> = (0..count)
+            .map(|_| random.next().is_multiple_of(3))
+            .collect();
+
+        (buttons, pressed)
+    }
+
+    fn count(presses: &[bool]) -> usize {
+        presses.iter().filter(|&&pressed| pressed).count()
+    }
+
+    #[test]
+    fn test_min_presses_large_machine() {
+        let len = 60;
+        let (buttons, pressed) = random_machine(len, 70, 0x9e3779b97f4a7c15);
+        let target = lights(&pressed, &buttons, len);
+
+        let presses = min_presses(&target, &buttons).unwrap().unwrap();
+
+        assert_eq!(lights(&presses, &buttons, len), target);
+        assert!(count(&presses) <= count(&pressed));
+    }
+
+    #[test]
+    fn test_min_presses_many_redundant_buttons() {
+        let (buttons, pressed) = random_machine(4, 100, 0x2545f4914f6cdd1d);
+        let target = lights(&pressed, &buttons, 4);
+
+        let presses = min_presses(&target, &buttons).unwrap().unwrap();
+
+        assert_eq!(lights(&presses, &buttons, 4), target);
+        // each light needs at most one press of its own
+        assert!(count(&presses) <= 4);
+    }
+
+    #[test]
+    fn test_searches_agree() {
+        for seed in 1..20 {
+            let (buttons, pressed) = random_machine(8, 14, seed);
+            let reduced = Reduced::new(&lights(&pressed, &buttons, 8), &buttons).unwrap();
+
+            let (walked, searched) = (reduced.walk_null_space(), reduced.breadth_first());
+
+            assert_eq!(lights(&walked, &buttons, 8), lights(&searched, &buttons, 8));
+            assert_eq!(count(&walked), count(&searched), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_min_presses_too_large() {
+        let (buttons, pressed) = random_machine(40, 80, 0x9e3779b97f4a7c15);
+        let target = lights(&pressed, &buttons, 40);
+
+        assert_eq!(
+            min_presses(&target, &buttons),
+            Err(SearchTooLarge {
+                independent: 40,
+                redundant: 40,
+                max_independent: 19,
+                max_redundant: 26,
+            })
+        );
+    }
+}
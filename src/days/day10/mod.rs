@@ -1,17 +1,22 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    str::FromStr,
-};
+use std::{fmt::Display, str::FromStr};
+
+use itertools::Itertools;
 
-use anyhow::Context;
+use crate::{
+    parse::{Line, ParseError, lines},
+    solution::{Part, Solution},
+};
 
 #[derive(Debug, PartialEq, Eq)]
-struct Machine {
+pub struct Machine {
     indicator_light_diagram: Vec<bool>,
     button_wiring_schematics: Vec<Vec<usize>>,
     joltage_requirements: Vec<usize>,
 }
 
+mod joltage;
+mod lights;
+
 fn press(light_diagram: &[bool], button: &[usize]) -> Vec<bool> {
     let mut updated_light_diagram = light_diagram.to_vec();
 
@@ -22,255 +27,482 @@ fn press(light_diagram: &[bool], button: &[usize]) -> Vec<bool> {
     updated_light_diagram
 }
 
-fn is_correctly_configured(machine: &Machine, light_diagram: &[bool]) -> bool {
-    machine.indicator_light_diagram.eq(light_diagram)
-}
+impl Machine {
+    pub fn new(
+        indicator_light_diagram: Vec<bool>,
+        button_wiring_schematics: Vec<Vec<usize>>,
+        joltage_requirements: Vec<usize>,
+    ) -> Self {
+        Self {
+            indicator_light_diagram,
+            button_wiring_schematics,
+            joltage_requirements,
+        }
+    }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct ButtonPressState {
-    indicator_light_diagram: Vec<bool>,
-    presses: usize,
-}
+    pub fn indicator_light_diagram(&self) -> &[bool] {
+        &self.indicator_light_diagram
+    }
 
-impl Machine {
-    fn min_button_presses_to_configure_indicator_lights(&self) -> usize {
-        let mut queue = VecDeque::new();
-        let mut seen: HashSet<Vec<bool>> = HashSet::new();
+    pub fn button_wiring_schematics(&self) -> &[Vec<usize>] {
+        &self.button_wiring_schematics
+    }
 
-        let initial = ButtonPressState {
-            indicator_light_diagram: vec![false; self.indicator_light_diagram.len()],
-            presses: 0,
-        };
-        seen.insert(initial.indicator_light_diagram.clone());
-        queue.push_front(initial);
+    pub fn joltage_requirements(&self) -> &[usize] {
+        &self.joltage_requirements
+    }
 
-        loop {
-            let ButtonPressState {
-                presses,
-                indicator_light_diagram,
-            } = queue.pop_front().unwrap();
+    /// Why the machine can't be configured at all, if it can't: its buttons and joltage
+    /// requirements must only refer to lights it has.
+    fn validate(&self) -> Result<(), String> {
+        let lights = self.indicator_light_diagram.len();
+
+        for (index, button) in self.button_wiring_schematics.iter().enumerate() {
+            if let Some(light) = button.iter().find(|&&light| light >= lights) {
+                return Err(format!(
+                    "button {} {} is wired to light {}, but there are only {} lights",
+                    index + 1,
+                    list(button, '(', ')'),
+                    light,
+                    lights
+                ));
+            }
+        }
 
-            for button in &self.button_wiring_schematics {
-                let updated_light_diagram = press(&indicator_light_diagram, button);
+        if self.joltage_requirements.len() != lights {
+            return Err(format!(
+                "expected a joltage requirement for each of {} lights, got {}",
+                lights,
+                self.joltage_requirements.len()
+            ));
+        }
 
-                if is_correctly_configured(self, &updated_light_diagram) {
-                    return presses + 1;
-                }
+        Ok(())
+    }
 
-                let state = ButtonPressState {
-                    indicator_light_diagram: updated_light_diagram,
-                    presses: presses + 1,
-                };
-                if seen.insert(state.indicator_light_diagram.clone()) {
-                    queue.push_back(state);
-                }
+    /// How many times to press each button, fewest in total, to light the indicator light
+    /// diagram. No button is pressed more than once, as a second press undoes the first.
+    pub fn configure_indicator_lights(&self) -> Configuration {
+        if let Err(reason) = self.validate() {
+            return Configuration::Invalid(reason);
+        }
+
+        match lights::min_presses(
+            &self.indicator_light_diagram,
+            &self.button_wiring_schematics,
+        ) {
+            Ok(Some(presses)) => {
+                Configuration::Solved(presses.into_iter().map(usize::from).collect())
             }
+            Ok(None) => Configuration::Unreachable,
+            Err(error) => Configuration::TooLarge(error.to_string()),
         }
     }
 
-    fn min_button_presses_to_configure_joltage_level_counters(&self) -> usize {
-        let mut buttons_by_counter: Vec<(usize, Vec<usize>)> = (0..self.joltage_requirements.len())
-            .map(|counter| {
-                (
-                    counter,
-                    self.button_wiring_schematics
-                        .iter()
-                        .enumerate()
-                        .filter(|(_, buttons)| buttons.contains(&counter))
-                        .map(|(i, _)| i)
-                        .collect(),
-                )
-            })
-            .collect();
-
-        let mut joltage = vec![0usize; self.joltage_requirements.len()];
-        let mut presses = 0;
-
-        // e.g. (0,1) (2) (0,2,3) {21,1,163,20}
-        // 1. We know only (0,1) contains 1, so we _must_ have exactly 1 press of this button.
-        // 2. We also know only (0,2,3) contains 3, so we _must_ have exactly 20 presses of this button
-        // 3. This leaves 0 and 2
-        // 4. With just (2) remaining, we should know _all_ remaining presses must be this button
-
-        // e.g.2 (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7} -> 10
-        // 1. 2 buttons make 0
-        //      need 3x (0,2) and (0,1)
-        //    2 buttons make 1
-        //      need 5x (1,3) and (0,1)
-        //    3 buttons make 2
-        //      need 4x (2), (2,3) and (0,2)
-        //    3 buttons make 3
-        //      need 7x (3), (1,3) and (2,3)
-
-        while let Some((i, buttons)) = buttons_by_counter
-            .iter()
-            .find(|(_, buttons)| buttons.len() == 1)
-        {
-            let remaining = self.joltage_requirements[*i] - joltage[*i];
-            let button = buttons[0];
-            presses += remaining;
+    /// How many times to press each button, fewest in total, to meet the joltage requirements.
+    pub fn configure_joltage_level_counters(&self) -> Configuration {
+        if let Err(reason) = self.validate() {
+            return Configuration::Invalid(reason);
+        }
+
+        match joltage::min_presses(&self.joltage_requirements, &self.button_wiring_schematics) {
+            Some(presses) => Configuration::Solved(presses),
+            None => Configuration::Unreachable,
+        }
+    }
 
-            let counters = &self.button_wiring_schematics[button];
+    pub fn configure(&self, part: Part) -> Configuration {
+        match part {
+            Part::One => self.configure_indicator_lights(),
+            Part::Two => self.configure_joltage_level_counters(),
+        }
+    }
+
+    /// Fewest presses lighting the indicator light diagram, or `None` if no presses do.
+    pub fn min_button_presses_to_configure_indicator_lights(&self) -> Option<usize> {
+        self.configure_indicator_lights().total()
+    }
 
-            for counter in counters {
-                joltage[*counter] += remaining;
+    /// Fewest presses meeting the joltage requirements, or `None` if no presses do.
+    pub fn min_button_presses_to_configure_joltage_level_counters(&self) -> Option<usize> {
+        self.configure_joltage_level_counters().total()
+    }
+
+    /// What `part` configures the machine to, as written in its description.
+    fn target(&self, part: Part) -> String {
+        match part {
+            Part::One => light_diagram(&self.indicator_light_diagram),
+            Part::Two => list(&self.joltage_requirements, '{', '}'),
+        }
+    }
+
+    /// What pressing each button `presses[i]` times configures the machine to for `part`.
+    fn replay(&self, part: Part, presses: &[usize]) -> String {
+        match part {
+            Part::One => light_diagram(&self.replay_indicator_lights(presses)),
+            Part::Two => list(&self.replay_joltage_level_counters(presses), '{', '}'),
+        }
+    }
+
+    /// The indicator lights after pressing each button `presses[i]` times, starting from off.
+    pub fn replay_indicator_lights(&self, presses: &[usize]) -> Vec<bool> {
+        let mut lights = vec![false; self.indicator_light_diagram.len()];
+
+        for (&count, button) in presses.iter().zip(&self.button_wiring_schematics) {
+            for _ in 0..count {
+                lights = press(&lights, button);
             }
+        }
 
-            buttons_by_counter.iter_mut().for_each(|(_, buttons)| {
-                if let Some(index) = buttons.iter().position(|value| *value == button) {
-                    buttons.swap_remove(index);
-                }
-            });
+        lights
+    }
+
+    /// The joltage level counters after pressing each button `presses[i]` times, starting
+    /// from zero.
+    pub fn replay_joltage_level_counters(&self, presses: &[usize]) -> Vec<usize> {
+        let mut counters = vec![0; self.joltage_requirements.len()];
+
+        for (&count, button) in presses.iter().zip(&self.button_wiring_schematics) {
+            for &counter in button {
+                counters[counter] += count;
+            }
+        }
+
+        counters
+    }
+}
+
+fn light_diagram(lights: &[bool]) -> String {
+    let lights: String = lights
+        .iter()
+        .map(|&on| if on { '#' } else { '.' })
+        .collect();
+
+    format!("[{}]", lights)
+}
+
+fn list(numbers: &[usize], open: char, close: char) -> String {
+    format!("{}{}{}", open, numbers.iter().join(","), close)
+}
+
+/// The outcome of configuring a machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Configuration {
+    /// How many times to press each button, fewest in total.
+    Solved(Vec<usize>),
+    /// No presses of the buttons configure the machine.
+    Unreachable,
+    /// The machine's description contradicts itself, e.g. a button wired to a missing light.
+    Invalid(String),
+    /// There are too many buttons to search for the fewest presses in reasonable time.
+    TooLarge(String),
+}
+
+impl Configuration {
+    /// Total presses, if the machine can be configured.
+    pub fn total(&self) -> Option<usize> {
+        match self {
+            Configuration::Solved(presses) => Some(presses.iter().sum()),
+            _ => None,
+        }
+    }
+}
+
+/// Why a machine on `line` can't be configured for `part`, or `None` if it can.
+fn problem(
+    line: usize,
+    machine: &Machine,
+    configuration: &Configuration,
+    part: Part,
+) -> Option<String> {
+    match configuration {
+        Configuration::Solved(_) => None,
+        Configuration::Unreachable => Some(format!(
+            "line {}: no button presses give {}",
+            line,
+            machine.target(part)
+        )),
+        Configuration::Invalid(reason) => {
+            Some(format!("line {}: invalid machine: {}", line, reason))
+        }
+        Configuration::TooLarge(reason) => Some(format!("line {}: {}", line, reason)),
+    }
+}
+
+/// Fewest presses configuring every machine for `part`, or an error listing each machine that
+/// can't be configured.
+pub fn total_presses(machines: &[Machine], part: Part) -> anyhow::Result<usize> {
+    let mut total = 0;
+    let mut problems = Vec::new();
+
+    for (index, machine) in machines.iter().enumerate() {
+        let configuration = machine.configure(part);
+
+        match configuration.total() {
+            Some(presses) => total += presses,
+            None => problems.extend(problem(index + 1, machine, &configuration, part)),
         }
+    }
+
+    if !problems.is_empty() {
+        anyhow::bail!(
+            "{} of {} machines can't be configured; {}",
+            problems.len(),
+            machines.len(),
+            problems.join("; ")
+        );
+    }
+
+    Ok(total)
+}
 
-        // if self.joltage_requirements.iter().eq(&vec![21, 1, 163, 20]) {
-        //     dbg!(buttons_by_counter);
-        //     dbg!(&joltage);
-        // }
+/// Describes the fewest button presses configuring each machine for `part`, checking each by
+/// replaying the presses.
+pub fn explain(machines: &[Machine], part: Part) -> anyhow::Result<Vec<String>> {
+    let mut explanations = Vec::new();
+    let mut total = 0;
+    let mut unconfigured = 0;
+
+    for (index, machine) in machines.iter().enumerate() {
+        let line = index + 1;
+        let configuration = machine.configure(part);
+
+        let Configuration::Solved(presses) = &configuration else {
+            unconfigured += 1;
+            explanations.extend(problem(line, machine, &configuration, part));
+            continue;
+        };
 
-        if joltage.iter().eq(&self.joltage_requirements) {
-            return presses;
+        let (target, reached) = (machine.target(part), machine.replay(part, presses));
+
+        if reached != target {
+            anyhow::bail!(
+                "line {}: pressing the buttons gives {} rather than {}",
+                line,
+                reached,
+                target
+            );
         }
 
-        let remainder: Vec<usize> = joltage
+        let pressed = presses
             .iter()
-            .zip(&self.joltage_requirements)
-            .map(|(current, remainder)| remainder - current)
-            .collect();
-
-        // for (i, buttons) in buttons_by_counter {
-        //     let requirement = self.joltage_requirements[i];
-
-        //     if buttons.len() == 1 {
-        //         let button = buttons[0];
-        //         presses += requirement;
-
-        //         let counters = &self.button_wiring_schematics[button];
-
-        //         for counter in counters {
-        //             joltage[*counter] += requirement;
-        //         }
-
-        //         // for _ in 0..buttons_by_counter.len() {
-        //         //     let buttons = buttons_by_counter[button];
-
-        //         //     if buttons.1.contains(&button) {
-        //         //         buttons.1.remove(button);
-        //         //     }
-        //         // }
-        //     } else {
-        //         // make up `requirement` from any combination of `buttons`
-        //         // e.g. max of button 1 is 10, max of button 2 is 5, requirement is 10,
-        //         // could be 5x1 and 5x2, or 6x1 and 4x2 etc.
-
-        //         // ideas:
-        //         // 1. prioritise smaller requirements first
-        //         // 2. prioritise using buttons affecting more counters
-
-        //         // TODO reframe the problem as "X lots of button a, b, c, Y lots of button b,c d etc."
-
-        //         let mut i = 0;
-
-        //         let mut queue: VecDeque<(Vec<usize>, usize)> = VecDeque::new();
-        //         let mut seen: HashSet<(Vec<usize>, usize)> = HashSet::new();
-
-        //         queue.push_back((joltage.clone(), presses));
-        //         seen.insert((joltage, presses));
-
-        //         loop {
-        //             let (joltage_level_counters, presses) = queue.pop_front().unwrap();
-
-        //             i += 1;
-
-        //             let max_presses_per_button: Vec<usize> = self
-        //                 .button_wiring_schematics
-        //                 .iter()
-        //                 .map(|button| {
-        //                     joltage_level_counters
-        //                         .iter()
-        //                         .enumerate()
-        //                         .filter_map(|(i, &current)| {
-        //                             if !button.contains(&i) {
-        //                                 None
-        //                             } else {
-        //                                 let requirement = self.joltage_requirements[i];
-        //                                 if requirement > current {
-        //                                     Some(requirement - current)
-        //                                 } else {
-        //                                     None
-        //                                 }
-        //                             }
-        //                         })
-        //                         .min()
-        //                         .unwrap_or_default()
-        //                 })
-        //                 .collect();
-
-        //             for (_, button) in max_presses_per_button
-        //                 .iter()
-        //                 .zip(&self.button_wiring_schematics)
-        //                 .filter(|(max, _)| **max != 0)
-        //             {
-        //                 let mut updated_joltage_levels = joltage_level_counters.clone();
-
-        //                 for &counter in button {
-        //                     updated_joltage_levels[counter] += 1;
-        //                 }
-
-        //                 if updated_joltage_levels.iter().eq(&self.joltage_requirements) {
-        //                     dbg!(i);
-        //                     return presses + 1;
-        //                 }
-
-        //                 if !seen.contains(&(updated_joltage_levels.clone(), presses + 1)) {
-        //                     queue.push_back((updated_joltage_levels.clone(), presses + 1));
-        //                     seen.insert((updated_joltage_levels, presses + 1));
-        //                 }
-        //             }
-        //         }
-        //     }
-        // }
-
-        1
+            .zip(&machine.button_wiring_schematics)
+            .filter(|(count, _)| **count > 0)
+            .map(|(count, button)| format!("{} × {}", count, list(button, '(', ')')))
+            .join(", ");
+
+        let count: usize = presses.iter().sum();
+        total += count;
+
+        explanations.push(format!(
+            "line {}: {} = {} presses, giving {}",
+            line,
+            if pressed.is_empty() {
+                "nothing"
+            } else {
+                &pressed
+            },
+            count,
+            reached
+        ));
+    }
+
+    explanations.push(format!("{} presses in total", total));
+
+    if unconfigured > 0 {
+        explanations.push(format!("{} machines can't be configured", unconfigured));
     }
+
+    Ok(explanations)
 }
 
 impl FromStr for Machine {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split(" ").collect();
+        Self::from_line(Line::new(1, s))
+    }
+}
 
-        let lights: &str = parts[0];
+/// Walks a line of input left to right, following the grammar
+///
+/// ```text
+/// machine = lights (space button)* space joltage
+/// lights  = "[" ("." | "#")* "]"
+/// button  = "(" (index ("," index)*)? ")"
+/// joltage = "{" (level ("," level)*)? "}"
+/// ```
+///
+/// where `space` is any run of whitespace, which may also surround the machine, and `index`
+/// and `level` are numbers.
+struct Cursor<'a> {
+    line: Line<'a>,
+    rest: &'a str,
+}
 
-        let indicator_light_diagram = lights[1..lights.len() - 1]
-            .chars()
-            .map(|c| match c {
-                '#' => Ok(true),
-                '.' => Ok(false),
-                _ => anyhow::bail!("expected . or #"),
-            })
-            .collect::<Result<Vec<bool>, Self::Err>>()?;
+impl<'a> Cursor<'a> {
+    fn new(line: Line<'a>) -> Self {
+        Self {
+            line,
+            rest: line.text,
+        }
+    }
 
-        let button_wiring_schematics = parts[1..parts.len() - 1]
-            .iter()
-            .map(|&part| {
-                part[1..part.len() - 1]
-                    .split(",")
-                    .map(|button| {
-                        button
-                            .parse::<usize>()
-                            .context(format!("expected {} to be a digit", button))
-                    })
-                    .collect()
-            })
-            .collect::<Result<Vec<Vec<usize>>, Self::Err>>()?;
+    fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    /// Skips whitespace, returning whether there was any.
+    fn skip_whitespace(&mut self) -> bool {
+        let trimmed = self.rest.trim_start();
+        let skipped = trimmed.len() < self.rest.len();
+
+        self.rest = trimmed;
+        skipped
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        match self.rest.strip_prefix(expected) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Error pointing at the next character, or the end of the line.
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        let next = self.peek().map_or(0, char::len_utf8);
 
-        let joltage_requirements = parts[parts.len() - 1];
-        let joltage_requirements = joltage_requirements[1..joltage_requirements.len() - 1]
-            .split(",")
-            .map(|s| str::parse::<usize>(s).context(format!("expected {} to be a usize digit", s)))
-            .collect::<Result<Vec<usize>, Self::Err>>()?;
+        self.line.error(&self.rest[..next], message)
+    }
+
+    fn expect(&mut self, expected: char, message: &str) -> Result<(), ParseError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    /// The part of the line from `start` up to the cursor.
+    fn since(&self, start: &'a str) -> &'a str {
+        &start[..start.len() - self.rest.len()]
+    }
+
+    fn number(&mut self, expected: &str) -> Result<(usize, &'a str), ParseError> {
+        let start = self.rest;
+        let digits = start.len() - start.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+        if digits == 0 {
+            return Err(self.error(format!("expected {}", expected)));
+        }
+
+        self.rest = &start[digits..];
+
+        Ok((
+            self.line.parse(&start[..digits], expected)?,
+            &start[..digits],
+        ))
+    }
+
+    /// A list of numbers between `open` and `close`, e.g. `(1,3)` or `()`, along with where each
+    /// number was.
+    fn list(
+        &mut self,
+        open: char,
+        close: char,
+        expected: &str,
+    ) -> Result<Vec<(usize, &'a str)>, ParseError> {
+        self.expect(open, &format!("expected {}", open))?;
+
+        if self.eat(close) {
+            return Ok(Vec::new());
+        }
+
+        let mut numbers = vec![self.number(expected)?];
+
+        while !self.eat(close) {
+            self.expect(',', &format!("expected , or {}", close))?;
+            numbers.push(self.number(expected)?);
+        }
+
+        Ok(numbers)
+    }
+
+    fn lights(&mut self) -> Result<Vec<bool>, ParseError> {
+        self.expect('[', "expected an indicator light diagram")?;
+        let mut lights = Vec::new();
+
+        while !self.eat(']') {
+            match self.peek() {
+                Some('#') => lights.push(true),
+                Some('.') => lights.push(false),
+                _ => return Err(self.error("expected . or #")),
+            }
+
+            self.rest = &self.rest[1..];
+        }
+
+        Ok(lights)
+    }
+}
+
+impl Machine {
+    fn from_line(line: Line<'_>) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(line);
+
+        cursor.skip_whitespace();
+        let indicator_light_diagram = cursor.lights()?;
+        let lights = indicator_light_diagram.len();
+
+        let mut button_wiring_schematics = Vec::new();
+
+        let joltage_requirements = loop {
+            let separated = cursor.skip_whitespace();
+
+            match cursor.peek() {
+                None => return Err(cursor.error("expected joltage requirements")),
+                Some(_) if !separated => return Err(cursor.error("expected a space")),
+                Some('(') => {
+                    let button = cursor.list('(', ')', "a light index")?;
+
+                    if let Some((_, index)) = button.iter().find(|&&(light, _)| light >= lights) {
+                        return Err(
+                            line.error(index, format!("expected a light index below {}", lights))
+                        );
+                    }
+
+                    button_wiring_schematics
+                        .push(button.into_iter().map(|(light, _)| light).collect());
+                }
+                Some('{') => {
+                    let start = cursor.rest;
+                    let levels = cursor.list('{', '}', "a joltage requirement")?;
+
+                    if levels.len() != lights {
+                        return Err(line.error(
+                            cursor.since(start),
+                            format!("expected {} joltage requirements, one per light", lights),
+                        ));
+                    }
+
+                    break levels.into_iter().map(|(level, _)| level).collect();
+                }
+                Some(_) => {
+                    return Err(
+                        cursor.error("expected a button wiring schematic or joltage requirements")
+                    );
+                }
+            }
+        };
+
+        cursor.skip_whitespace();
+
+        if cursor.peek().is_some() {
+            return Err(cursor.error("expected end of line after the joltage requirements"));
+        }
 
         Ok(Self {
             indicator_light_diagram,
@@ -280,37 +512,51 @@ impl FromStr for Machine {
     }
 }
 
-fn parse(input: String) -> anyhow::Result<Vec<Machine>> {
-    input
-        .lines()
-        .map(str::parse::<Machine>)
-        .collect::<anyhow::Result<Vec<Machine>>>()
+impl Display for Machine {
+    /// Writes the machine as it's written in the input, so it parses back to the same machine
+    /// unless it's invalid, with a button wired to a missing light or the wrong number of joltage
+    /// requirements.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", light_diagram(&self.indicator_light_diagram))?;
+
+        for button in &self.button_wiring_schematics {
+            write!(f, " {}", list(button, '(', ')'))?;
+        }
+
+        write!(f, " {}", list(&self.joltage_requirements, '{', '}'))
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+    lines(input).map(Machine::from_line).collect()
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let machines = parse(input)?;
+pub struct Day10;
 
-    println!(
-        "part1: {}",
-        machines
-            .iter()
-            .map(|m| { m.min_button_presses_to_configure_indicator_lights() })
-            .sum::<usize>()
-    );
-
-    // println!(
-    //     "part2: {}",
-    //     machines
-    //         .iter()
-    //         .map(|m| { m.min_button_presses_to_configure_joltage_level_counters() })
-    //         .sum::<usize>()
-    // );
-
-    Ok(())
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input<'a> = Vec<Machine>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(machines: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        total_presses(machines, Part::One)
+    }
+
+    fn part2(machines: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        total_presses(machines, Part::Two)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::{collection::vec, prelude::*};
+
     use super::*;
 
     #[test]
@@ -330,7 +576,7 @@ mod tests {
 
         assert_eq!(
             machine.min_button_presses_to_configure_indicator_lights(),
-            2
+            Some(2)
         );
     }
 
@@ -350,7 +596,7 @@ mod tests {
 
         assert_eq!(
             machine.min_button_presses_to_configure_indicator_lights(),
-            3
+            Some(3)
         );
     }
 
@@ -369,12 +615,11 @@ mod tests {
 
         assert_eq!(
             machine.min_button_presses_to_configure_indicator_lights(),
-            2
+            Some(2)
         );
     }
 
     #[test]
-    #[ignore = "not yet implemented"]
     fn test_machine_min_button_presses_to_configure_joltage_level_counters() {
         let machine = Machine {
             indicator_light_diagram: vec![false, true, true, false],
@@ -391,7 +636,137 @@ mod tests {
 
         assert_eq!(
             machine.min_button_presses_to_configure_joltage_level_counters(),
-            10
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn test_infeasible_machines() {
+        let mut machines = parse(
+            "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[#.] (0,1) {1,2}",
+        )
+        .unwrap();
+
+        // parsing rejects invalid machines, but they can still be built by hand
+        machines.push(Machine::new(
+            vec![false, true],
+            vec![vec![0, 2]],
+            vec![1, 1],
+        ));
+        machines.push(Machine::new(vec![true, false], vec![vec![0]], vec![1]));
+
+        assert_eq!(
+            machines[1].configure_indicator_lights(),
+            Configuration::Unreachable
+        );
+        assert_eq!(
+            machines[1].configure_joltage_level_counters(),
+            Configuration::Unreachable
+        );
+        assert_eq!(
+            machines[2].configure_indicator_lights(),
+            Configuration::Invalid(
+                "button 1 (0,2) is wired to light 2, but there are only 2 lights".to_string()
+            )
+        );
+        assert_eq!(
+            machines[3].configure_joltage_level_counters(),
+            Configuration::Invalid(
+                "expected a joltage requirement for each of 2 lights, got 1".to_string()
+            )
+        );
+
+        assert_eq!(
+            total_presses(&machines, Part::Two).unwrap_err().to_string(),
+            "3 of 4 machines can't be configured; \
+             line 2: no button presses give {1,2}; \
+             line 3: invalid machine: button 1 (0,2) is wired to light 2, but there are only 2 lights; \
+             line 4: invalid machine: expected a joltage requirement for each of 2 lights, got 1"
+        );
+        assert_eq!(total_presses(&machines[..1], Part::Two).unwrap(), 10);
+
+        let explanation = explain(&machines, Part::One).unwrap();
+        assert_eq!(explanation[1], "line 2: no button presses give [#.]");
+        assert_eq!(explanation[5], "3 machines can't be configured");
+    }
+
+    #[test]
+    fn test_many_buttons() {
+        // 66 of the buttons are redundant, each doing what another does
+        let redundant = Machine::new(
+            vec![true, false, true, false],
+            (0..70).map(|button| vec![button % 4]).collect(),
+            vec![1; 4],
+        );
+
+        assert_eq!(
+            redundant.min_button_presses_to_configure_indicator_lights(),
+            Some(2)
+        );
+
+        // 40 independent buttons and 40 redundant ones, pairing up neighbouring lights
+        let large = Machine::new(
+            vec![true; 40],
+            (0..40)
+                .map(|light| vec![light])
+                .chain((0..40).map(|light| vec![light, (light + 1) % 40]))
+                .collect(),
+            vec![1; 40],
+        );
+
+        assert_eq!(
+            large.configure_indicator_lights(),
+            Configuration::TooLarge(
+                "too many buttons to search, with 40 independent and 40 redundant, where at most 19 \
+                 independent or 26 redundant can be searched"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            total_presses(&[redundant, large], Part::One)
+                .unwrap_err()
+                .to_string(),
+            "1 of 2 machines can't be configured; \
+             line 2: too many buttons to search, with 40 independent and 40 redundant, where at \
+             most 19 independent or 26 redundant can be searched"
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = include_str!("../../../examples/day10/example.txt");
+        let machines = parse(input).unwrap();
+
+        let explanation = explain(&machines, Part::Two).unwrap();
+
+        assert_eq!(explanation.len(), 4);
+        assert!(explanation[0].starts_with("line 1: "));
+        assert!(explanation[0].ends_with(" = 10 presses, giving {3,5,4,7}"));
+        assert_eq!(explanation[3], "33 presses in total");
+
+        assert_eq!(
+            explain(&machines[2..], Part::One).unwrap(),
+            vec![
+                "line 1: 1 × (0,3,4), 1 × (0,1,2,4,5) = 2 presses, giving [.###.#]",
+                "2 presses in total"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replay() {
+        let machine: Machine = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            machine.replay_indicator_lights(&[0, 0, 0, 0, 1, 3]),
+            vec![false, true, true, false]
+        );
+        assert_eq!(
+            machine.replay_joltage_level_counters(&[1, 3, 0, 3, 1, 2]),
+            vec![3, 5, 4, 7]
         );
     }
 
@@ -400,7 +775,7 @@ mod tests {
         let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}".to_string();
 
         assert_eq!(
-            parse(input).unwrap(),
+            parse(&input).unwrap(),
             vec![Machine {
                 indicator_light_diagram: vec![false, true, true, false],
                 button_wiring_schematics: vec![
@@ -415,4 +790,123 @@ mod tests {
             }]
         )
     }
+
+    #[test]
+    fn test_parse_errors() {
+        for (input, error) in [
+            (
+                "[.##.] (3) {3,5,4,7}\n[.#x] (0) {1,2,3}",
+                "line 2, column 4: expected . or #, found \"x\"",
+            ),
+            (
+                "[.##.] (3 {3,5,4,7}",
+                "line 1, column 10: expected , or ), found \" \"",
+            ),
+            (
+                "[.##.] (3) (1,a) {3,5,4,7}",
+                "line 1, column 15: expected a light index, found \"a\"",
+            ),
+            (
+                "[.##.]",
+                "line 1, column 7: expected joltage requirements, found end of line",
+            ),
+            (
+                "[.##.] (3) 3,5,4,7",
+                "line 1, column 12: expected a button wiring schematic or joltage requirements, \
+                 found \"3\"",
+            ),
+            (
+                "[.##.] (3) {3,5,4,7",
+                "line 1, column 20: expected , or }, found end of line",
+            ),
+            (
+                "[.##.](3) {3,5,4,7}",
+                "line 1, column 7: expected a space, found \"(\"",
+            ),
+            (
+                "[.##.] (3) {3,5,4,7} (2)",
+                "line 1, column 22: expected end of line after the joltage requirements, \
+                 found \"(\"",
+            ),
+            (
+                "[] {1}",
+                "line 1, column 4: expected 0 joltage requirements, one per light, found \"{1}\"",
+            ),
+            (
+                "[.##.] (1,4) {3,5,4,7}",
+                "line 1, column 11: expected a light index below 4, found \"4\"",
+            ),
+            (
+                "[.##.] (3) {3,5,4}",
+                "line 1, column 12: expected 4 joltage requirements, one per light, \
+                 found \"{3,5,4}\"",
+            ),
+            (
+                "[.##.] (,) {3,5,4,7}",
+                "line 1, column 9: expected a light index, found \",\"",
+            ),
+            (
+                "[.##.] (1,) {3,5,4,7}",
+                "line 1, column 11: expected a light index, found \")\"",
+            ),
+        ] {
+            assert_eq!(parse(input).unwrap_err().to_string(), error, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_empty_machines() {
+        let machines = parse("[] () {}\n[#.] () (0) {1,0}").unwrap();
+
+        assert_eq!(machines[0], Machine::new(vec![], vec![vec![]], vec![]));
+        assert_eq!(machines[0].to_string(), "[] () {}");
+        assert_eq!(machines[1].to_string(), "[#.] () (0) {1,0}");
+        assert_eq!(total_presses(&machines, Part::One).unwrap(), 1);
+        assert_eq!(total_presses(&machines, Part::Two).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_parse_whitespace() {
+        assert_eq!(
+            parse(" [.##.]   (3)\t(1,3)  {3,5,4,7}  ").unwrap(),
+            parse("[.##.] (3) (1,3) {3,5,4,7}").unwrap()
+        );
+    }
+
+    fn machine() -> impl Strategy<Value = Machine> {
+        (0..12usize)
+            .prop_flat_map(|lights| {
+                (
+                    vec(any::<bool>(), lights),
+                    // with no lights every button is empty, but the range of indices can't be
+                    vec(vec(0..lights.max(1), 0..=lights), 0..8),
+                    vec(0..1000usize, lights),
+                )
+            })
+            .prop_map(|(lights, buttons, joltage)| Machine::new(lights, buttons, joltage))
+    }
+
+    proptest! {
+        #[test]
+        fn test_display_round_trips(machine in machine()) {
+            prop_assert_eq!(machine.to_string().parse::<Machine>(), Ok(machine));
+        }
+
+        #[test]
+        fn test_parse_ignores_extra_whitespace(machine in machine(), spaces in vec("[ \t]+", 32)) {
+            let mut spaces = spaces.iter().cycle();
+            let spaced = machine
+                .to_string()
+                .split(' ')
+                .map(|part| format!("{}{}", spaces.next().unwrap(), part))
+                .join("");
+
+            prop_assert_eq!(spaced.parse::<Machine>(), Ok(machine));
+        }
+
+        #[test]
+        fn test_parse_never_panics(line in "[\\[\\]().#{},0-9 ]{0,40}") {
+            let _ = line.parse::<Machine>();
+        }
+    }
 }
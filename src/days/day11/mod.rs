@@ -2,20 +2,25 @@ use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-fn parse(input: &str) -> anyhow::Result<HashMap<&str, Vec<&str>>> {
-    input
-        .lines()
+use crate::{
+    parse::{ParseError, lines},
+    solution::Solution,
+};
+
+pub fn parse(input: &str) -> Result<HashMap<&str, Vec<&str>>, ParseError> {
+    lines(input)
         .map(|line| {
-            let parts = line.split_once(": ").ok_or(anyhow::format_err!(
-                "expected ': ' separated input and outputs"
-            ))?;
+            let parts = line
+                .text
+                .split_once(": ")
+                .ok_or(line.error(line.text, "expected ': ' separated input and outputs"))?;
 
             Ok((parts.0, parts.1.split_whitespace().collect()))
         })
         .collect()
 }
 
-fn paths<'a>(
+pub fn paths<'a>(
     cache: &mut HashMap<(&'a str, &'a str), usize>,
     devices: &HashMap<&'a str, Vec<&'a str>>,
     from: &'a str,
@@ -51,7 +56,7 @@ fn paths<'a>(
     }
 }
 
-fn paths_with_required_waypoints<'a>(
+pub fn paths_with_required_waypoints<'a>(
     cache: &mut HashMap<(&'a str, &'a str), usize>,
     devices: &'a HashMap<&str, Vec<&str>>,
     from: &'a str,
@@ -85,24 +90,32 @@ fn paths_with_required_waypoints<'a>(
     total
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let devices = parse(&input)?;
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
 
-    let mut cache = HashMap::new();
+    type Input<'a> = HashMap<&'a str, Vec<&'a str>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(parse(input)?)
+    }
 
-    println!("part1: {}", paths(&mut cache, &devices, "you", "out"));
-    println!(
-        "part2: {}",
-        paths_with_required_waypoints(
-            &mut cache,
-            &devices,
+    fn part1(devices: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        Ok(paths(&mut HashMap::new(), devices, "you", "out"))
+    }
+
+    fn part2(devices: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        Ok(paths_with_required_waypoints(
+            &mut HashMap::new(),
+            devices,
             "svr",
             "out",
-            HashSet::from(["dac", "fft"])
-        )
-    );
-
-    Ok(())
+            HashSet::from(["dac", "fft"]),
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -111,41 +124,14 @@ mod tests {
 
     #[test]
     fn test_paths() {
-        let devices = parse(
-            "aaa: you hhh
-you: bbb ccc
-bbb: ddd eee
-ccc: ddd eee fff
-ddd: ggg
-eee: out
-fff: out
-ggg: out
-hhh: ccc fff iii
-iii: out",
-        )
-        .unwrap();
+        let devices = parse(include_str!("../../../examples/day11/example1.txt")).unwrap();
 
         assert_eq!(paths(&mut HashMap::new(), &devices, "you", "out"), 5);
     }
 
     #[test]
     fn test_paths_with_required_waypoints() {
-        let devices = parse(
-            "svr: aaa bbb
-aaa: fft
-fft: ccc
-bbb: tty
-tty: ccc
-ccc: ddd eee
-ddd: hub
-hub: fff
-eee: dac
-dac: fff
-fff: ggg hhh
-ggg: out
-hhh: out",
-        )
-        .unwrap();
+        let devices = parse(include_str!("../../../examples/day11/example2.txt")).unwrap();
 
         assert_eq!(
             paths_with_required_waypoints(
@@ -158,4 +144,12 @@ hhh: out",
             2
         );
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("you: bbb\nbbb out").unwrap_err().to_string(),
+            "line 2, column 1: expected ': ' separated input and outputs, found \"bbb out\""
+        );
+    }
 }
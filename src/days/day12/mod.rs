@@ -1,15 +1,28 @@
-use std::{collections::HashSet, num::ParseIntError};
+use std::{collections::HashSet, convert::Infallible};
 
-type Present = Vec<Vec<bool>>;
+use crate::{
+    parse::{Line, ParseError, sections},
+    solution::{Part, Solution},
+};
+
+pub type Present = Vec<Vec<bool>>;
 
 #[derive(Debug)]
-struct Region {
+pub struct Region {
     width: usize,
     length: usize,
     presents: Vec<usize>,
 }
 
 impl Region {
+    pub fn new(width: usize, length: usize, presents: Vec<usize>) -> Self {
+        Self {
+            width,
+            length,
+            presents,
+        }
+    }
+
     pub fn can_fit(&self, presents: &[Present]) -> bool {
         // 1st heuristic: check if there are enough 3x3 bins for all presents first
         let bins: usize = self.presents.iter().sum();
@@ -51,42 +64,22 @@ impl Region {
     }
 }
 
-#[allow(dead_code)]
-fn rotate(present: &[Vec<bool>]) -> Present {
-    let length = present.len();
+pub fn rotate(present: &[Vec<bool>]) -> Present {
     let width = present[0].len();
-    let mut rotated = Vec::with_capacity(width);
 
-    for column_index in 0..width {
-        let mut row = Vec::with_capacity(length);
-        for row_index in (0..length).rev() {
-            row.push(present[row_index][column_index])
-        }
-        rotated.push(row);
-    }
-
-    rotated
+    (0..width)
+        .map(|column_index| present.iter().rev().map(|row| row[column_index]).collect())
+        .collect()
 }
 
-#[allow(dead_code)]
-fn flip(present: &[Vec<bool>]) -> Present {
-    let length = present.len();
-    let width = present[0].len();
-    let mut flipped = Vec::with_capacity(length);
-
-    for row_index in 0..length {
-        let mut row = Vec::with_capacity(width);
-        for column_index in (0..width).rev() {
-            row.push(present[row_index][column_index])
-        }
-        flipped.push(row)
-    }
-
-    flipped
+pub fn flip(present: &[Vec<bool>]) -> Present {
+    present
+        .iter()
+        .map(|row| row.iter().rev().copied().collect())
+        .collect()
 }
 
-#[allow(dead_code)]
-fn permutations(present: &[Vec<bool>]) -> HashSet<Present> {
+pub fn permutations(present: &[Vec<bool>]) -> HashSet<Present> {
     let mut permutations = HashSet::new();
 
     permutations.insert(present.to_vec());
@@ -113,57 +106,92 @@ fn permutations(present: &[Vec<bool>]) -> HashSet<Present> {
     permutations
 }
 
-fn parse(input: String) -> anyhow::Result<(Vec<Present>, Vec<Region>)> {
-    let parts: Vec<&str> = input.split("\n\n").collect();
+fn parse_present(section: &[Line<'_>]) -> Result<Present, ParseError> {
+    let (header, rows) = section.split_first().expect("sections are never empty");
 
-    let presents = parts[0..parts.len() - 1]
-        .iter()
-        .map(|present| {
-            present
-                .lines()
-                .skip(1)
-                .map(|line| line.chars().map(|c| c == '#').collect())
+    if !header.text.ends_with(':') {
+        return Err(header.error(header.text, "expected a present index such as '0:'"));
+    }
+
+    rows.iter()
+        .map(|row| {
+            row.text
+                .char_indices()
+                .map(|(i, c)| match c {
+                    '#' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(row.error(&row.text[i..i + c.len_utf8()], "expected . or #")),
+                })
                 .collect()
         })
-        .collect();
-
-    let regions = parts[parts.len() - 1]
-        .lines()
-        .map(|line| {
-            let (size, present_counts) = line.split_once(": ").ok_or(anyhow::format_err!(
-                "expected ': ' separated region size and present count"
-            ))?;
-
-            let (width, length) = size.split_once("x").ok_or(anyhow::format_err!(
-                "expected 'x' separated width and length"
-            ))?;
-
-            Ok(Region {
-                width: width.parse()?,
-                length: length.parse()?,
-                presents: present_counts
-                    .split_whitespace()
-                    .map(|count| count.parse())
-                    .collect::<Result<Vec<usize>, ParseIntError>>()?,
-            })
-        })
-        .collect::<anyhow::Result<Vec<Region>>>()?;
+        .collect()
+}
+
+fn parse_region(line: &Line<'_>) -> Result<Region, ParseError> {
+    let (size, present_counts) = line.text.split_once(": ").ok_or(line.error(
+        line.text,
+        "expected ': ' separated region size and present count",
+    ))?;
+
+    let (width, length) = size
+        .split_once("x")
+        .ok_or(line.error(size, "expected 'x' separated width and length"))?;
+
+    Ok(Region {
+        width: line.parse(width, "a width")?,
+        length: line.parse(length, "a length")?,
+        presents: present_counts
+            .split_whitespace()
+            .map(|count| line.parse(count, "a present count"))
+            .collect::<Result<Vec<usize>, _>>()?,
+    })
+}
+
+pub fn parse(input: &str) -> Result<(Vec<Present>, Vec<Region>), ParseError> {
+    let mut sections = sections(input);
+
+    let regions = sections.pop().ok_or(ParseError::new(
+        1,
+        1,
+        "",
+        "expected presents followed by regions",
+    ))?;
+
+    let presents = sections
+        .iter()
+        .map(|section| parse_present(section))
+        .collect::<Result<_, _>>()?;
+
+    let regions = regions.iter().map(parse_region).collect::<Result<_, _>>()?;
 
     Ok((presents, regions))
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let (presents, regions) = parse(input)?;
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    const PARTS: &'static [Part] = &[Part::One];
 
-    println!(
-        "part1: {}",
-        regions
+    type Input<'a> = (Vec<Present>, Vec<Region>);
+    type Part1 = usize;
+    type Part2 = Infallible;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1((presents, regions): &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        Ok(regions
             .iter()
-            .filter(|region| region.can_fit(&presents))
-            .count()
-    );
+            .filter(|region| region.can_fit(presents))
+            .count())
+    }
 
-    Ok(())
+    fn part2(_: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        anyhow::bail!("day 12 has no part 2")
+    }
 }
 
 #[cfg(test)]
@@ -267,4 +295,32 @@ mod tests {
 
         assert!(region.can_fit(&presents));
     }
+
+    #[test]
+    fn test_parse() {
+        let (presents, regions) = parse("0:\n##\n#.\n\n1:\n#\n\n4x4: 0 2\n12x5: 1 0").unwrap();
+
+        assert_eq!(
+            presents,
+            vec![vec![vec![true, true], vec![true, false]], vec![vec![true]]]
+        );
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[1].presents, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("").unwrap_err().to_string(),
+            "line 1, column 1: expected presents followed by regions, found end of line"
+        );
+        assert_eq!(
+            parse("0:\n#x\n\n4x4: 1").unwrap_err().to_string(),
+            "line 2, column 2: expected . or #, found \"x\""
+        );
+        assert_eq!(
+            parse("0:\n#\n\n4x4: 1\n4y4: 1").unwrap_err().to_string(),
+            "line 5, column 1: expected 'x' separated width and length, found \"4y4\""
+        );
+    }
 }
@@ -1,25 +1,35 @@
 use std::ops::RangeInclusive;
 
-type IDRange = RangeInclusive<ID>;
-
-type ID = usize;
-
-fn parse_id_ranges(ranges: String) -> anyhow::Result<Vec<IDRange>> {
-    ranges
-        .split(",")
-        .map(|value| match value.trim().split_once("-") {
-            Some((start, end)) => {
-                let start: usize = start.parse()?;
-                let end: usize = end.parse()?;
-
-                Ok(RangeInclusive::new(start, end))
-            }
-            None => anyhow::bail!("expected ID range to be '-' separated"),
+use crate::{
+    parse::{ParseError, lines},
+    solution::Solution,
+};
+
+pub type IDRange = RangeInclusive<ID>;
+
+pub type ID = usize;
+
+pub fn parse_id_ranges(ranges: &str) -> Result<Vec<IDRange>, ParseError> {
+    lines(ranges)
+        .flat_map(|line| {
+            line.text
+                .split(",")
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(move |value| match value.split_once("-") {
+                    Some((start, end)) => {
+                        let start: usize = line.parse(start, "a start ID")?;
+                        let end: usize = line.parse(end, "an end ID")?;
+
+                        Ok(RangeInclusive::new(start, end))
+                    }
+                    None => Err(line.error(value, "expected ID range to be '-' separated")),
+                })
         })
         .collect()
 }
 
-fn invalid_ids_with_exactly_one_repeated_sequence(range: IDRange) -> impl Iterator<Item = ID> {
+pub fn invalid_ids_with_exactly_one_repeated_sequence(range: IDRange) -> impl Iterator<Item = ID> {
     range.filter(|id| {
         let s: Vec<char> = id.to_string().chars().collect();
 
@@ -39,7 +49,9 @@ fn invalid_ids_with_exactly_one_repeated_sequence(range: IDRange) -> impl Iterat
     })
 }
 
-fn invalid_ids_with_at_least_two_repeated_sequences(range: IDRange) -> impl Iterator<Item = ID> {
+pub fn invalid_ids_with_at_least_two_repeated_sequences(
+    range: IDRange,
+) -> impl Iterator<Item = ID> {
     range.filter(|id| {
         let s: Vec<char> = id.to_string().chars().collect();
 
@@ -64,22 +76,30 @@ fn invalid_ids_with_at_least_two_repeated_sequences(range: IDRange) -> impl Iter
     })
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let ranges = parse_id_ranges(input)?;
+pub struct Day2;
 
-    println!(
-        "part 1: {}",
-        ranges.iter().cloned().fold(0, |acc, range| acc
-            + invalid_ids_with_exactly_one_repeated_sequence(range).sum::<usize>())
-    );
+impl Solution for Day2 {
+    const DAY: u8 = 2;
 
-    println!(
-        "part 2: {}",
-        ranges.iter().cloned().fold(0, |acc, range| acc
-            + invalid_ids_with_at_least_two_repeated_sequences(range).sum::<usize>())
-    );
+    type Input<'a> = Vec<IDRange>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(parse_id_ranges(input)?)
+    }
+
+    fn part1(ranges: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        Ok(ranges.iter().cloned().fold(0, |acc, range| {
+            acc + invalid_ids_with_exactly_one_repeated_sequence(range).sum::<usize>()
+        }))
+    }
 
-    Ok(())
+    fn part2(ranges: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        Ok(ranges.iter().cloned().fold(0, |acc, range| {
+            acc + invalid_ids_with_at_least_two_repeated_sequences(range).sum::<usize>()
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -88,9 +108,9 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124".to_string();
+        let input = include_str!("../../../examples/day2/example.txt").to_string();
 
-        let ranges = parse_id_ranges(input).unwrap();
+        let ranges = parse_id_ranges(&input).unwrap();
 
         assert_eq!(
             ranges
@@ -104,9 +124,9 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124".to_string();
+        let input = include_str!("../../../examples/day2/example.txt").to_string();
 
-        let ranges = parse_id_ranges(input).unwrap();
+        let ranges = parse_id_ranges(&input).unwrap();
 
         assert_eq!(
             ranges
@@ -120,4 +140,16 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_id_ranges("11-22, 95115").unwrap_err().to_string(),
+            "line 1, column 8: expected ID range to be '-' separated, found \"95115\""
+        );
+        assert_eq!(
+            parse_id_ranges("11-22,95-x").unwrap_err().to_string(),
+            "line 1, column 10: expected an end ID, found \"x\""
+        );
+    }
 }
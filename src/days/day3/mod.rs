@@ -1,29 +1,46 @@
 use std::str::FromStr;
 
+use crate::{
+    parse::{Line, ParseError, lines},
+    solution::Solution,
+};
+
 #[derive(Debug)]
 struct Battery {
     joltage: u8,
 }
 
 #[derive(Debug)]
-struct Bank {
+pub struct Bank {
     batteries: Vec<Battery>,
 }
 
 impl FromStr for Bank {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            batteries: s
-                .bytes()
-                .map(|b| Ok(Battery { joltage: b - 48 }))
-                .collect::<anyhow::Result<Vec<_>>>()?,
-        })
+        Self::from_line(Line::new(1, s))
     }
 }
 
 impl Bank {
+    fn from_line(line: Line<'_>) -> Result<Self, ParseError> {
+        Ok(Self {
+            batteries: line
+                .text
+                .char_indices()
+                .map(|(i, c)| match c.to_digit(10) {
+                    Some(joltage) => Ok(Battery {
+                        joltage: joltage as u8,
+                    }),
+                    None => {
+                        Err(line.error(&line.text[i..i + c.len_utf8()], "expected a joltage digit"))
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+
     pub fn max_joltage(&self, batteries: usize) -> usize {
         if batteries > self.batteries.len() {
             return 0;
@@ -59,23 +76,36 @@ impl Bank {
     }
 }
 
-fn parse(input: String) -> anyhow::Result<Vec<Bank>> {
-    input.lines().map(str::parse::<Bank>).collect()
+pub fn parse(input: &str) -> Result<Vec<Bank>, ParseError> {
+    lines(input).map(Bank::from_line).collect()
 }
 
-fn total_joltage(banks: &[Bank], batteries: usize) -> usize {
+pub fn total_joltage(banks: &[Bank], batteries: usize) -> usize {
     banks
         .iter()
         .fold(0, |acc, bank| acc + bank.max_joltage(batteries))
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let banks = parse(input)?;
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
 
-    println!("part1: {}", total_joltage(&banks, 2));
-    println!("part2: {}", total_joltage(&banks, 12));
+    type Input<'a> = Vec<Bank>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    Ok(())
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(banks: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        Ok(total_joltage(banks, 2))
+    }
+
+    fn part2(banks: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        Ok(total_joltage(banks, 12))
+    }
 }
 
 #[cfg(test)]
@@ -86,28 +116,28 @@ mod tests {
     fn test_small_bank() {
         let input = "9876".to_string();
 
-        assert_eq!(total_joltage(&parse(input).unwrap(), 2), 98)
+        assert_eq!(total_joltage(&parse(&input).unwrap(), 2), 98)
     }
 
     #[test]
     fn test_part1() {
-        let input = "987654321111111
-811111111111119
-234234234234278
-818181911112111"
-            .to_string();
+        let input = include_str!("../../../examples/day3/example.txt").to_string();
 
-        assert_eq!(total_joltage(&parse(input).unwrap(), 2), 357);
+        assert_eq!(total_joltage(&parse(&input).unwrap(), 2), 357);
     }
 
     #[test]
     fn test_part2() {
-        let input = "987654321111111
-811111111111119
-234234234234278
-818181911112111"
-            .to_string();
+        let input = include_str!("../../../examples/day3/example.txt").to_string();
+
+        assert_eq!(total_joltage(&parse(&input).unwrap(), 12), 3121910778619);
+    }
 
-        assert_eq!(total_joltage(&parse(input).unwrap(), 12), 3121910778619);
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("9876\n98/6").unwrap_err().to_string(),
+            "line 2, column 3: expected a joltage digit, found \"/\""
+        );
     }
 }
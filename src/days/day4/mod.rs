@@ -1,91 +1,334 @@
-use std::collections::HashSet;
+use itertools::Either;
 
-fn parse(input: String) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|line| line.trim().chars().collect())
-        .collect()
+use crate::{
+    grid::{Grid, ORTHOGONAL, Position, SURROUNDING},
+    parse::ParseError,
+    solution::{Part, Solution},
+};
+
+pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(input, Some, "a roll of paper or empty space")
 }
 
-pub fn accessible_rolls_of_paper(grid: &[Vec<char>]) -> impl Iterator<Item = (usize, usize)> {
-    grid.iter().enumerate().flat_map(move |(row_index, row)| {
-        row.iter()
-            .enumerate()
-            .filter_map(move |(column_index, position)| {
-                if *position != '@' {
-                    return None;
-                }
+/// Which cells count as the neighbours of a cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The 8 cells sharing an edge or a corner.
+    Moore,
+    /// The 4 cells sharing an edge.
+    VonNeumann,
+    /// Cells at these `(row, column)` offsets.
+    Custom(Vec<(isize, isize)>),
+}
 
-                let mut adjacent_paper_rolls = 0;
-
-                for row_offset in -1isize..=1 {
-                    if row_index == 0 && row_offset == -1
-                        || row_index == grid.len() - 1 && row_offset == 1
-                    {
-                        continue;
-                    }
-
-                    for column_offset in -1isize..=1 {
-                        if column_index == 0 && column_offset == -1
-                            || column_index == grid[0].len() - 1 && column_offset == 1
-                        {
-                            continue;
-                        }
-
-                        if column_index == 0 && row_index == 0 {
-                            continue;
-                        }
-
-                        if grid[row_index.strict_add_signed(row_offset)]
-                            [column_index.strict_add_signed(column_offset)]
-                            == '@'
-                        {
-                            adjacent_paper_rolls += 1
-                        }
-                    }
-                }
+impl Neighbourhood {
+    pub fn offsets(&self) -> &[(isize, isize)] {
+        match self {
+            Neighbourhood::Moore => &SURROUNDING,
+            Neighbourhood::VonNeumann => &ORTHOGONAL,
+            Neighbourhood::Custom(offsets) => offsets,
+        }
+    }
+}
+
+/// When a roll of paper can be reached by a forklift: when fewer than `threshold` of its
+/// neighbours are rolls too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// Characters that are rolls of paper; anything else is empty space.
+    pub rolls: Vec<char>,
+    pub neighbourhood: Neighbourhood,
+    pub threshold: usize,
+    /// Whether neighbours wrap around the edges of the grid, rather than stopping at them.
+    pub wrap: bool,
+}
+
+impl std::str::FromStr for Neighbourhood {
+    type Err = anyhow::Error;
+
+    /// `moore`, `von-neumann`, or `;` separated `row:column` offsets such as `-1:0;0:2`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "moore" => Ok(Neighbourhood::Moore),
+            "von-neumann" => Ok(Neighbourhood::VonNeumann),
+            _ => value
+                .split(';')
+                .map(|offset| {
+                    let (row, column) = offset.split_once(':').ok_or(anyhow::format_err!(
+                        "expected neighbourhood of moore, von-neumann or row:column offsets, \
+                         got {}",
+                        value
+                    ))?;
+
+                    Ok((row.parse()?, column.parse()?))
+                })
+                .collect::<anyhow::Result<_>>()
+                .map(Neighbourhood::Custom),
+        }
+    }
+}
 
-                if adjacent_paper_rolls <= 4 {
-                    Some((row_index, column_index))
-                } else {
-                    None
+impl Default for Rule {
+    /// The puzzle's rule: `@` rolls with fewer than 4 rolls in the 8 cells around them.
+    fn default() -> Self {
+        Self {
+            rolls: vec!['@'],
+            neighbourhood: Neighbourhood::Moore,
+            threshold: 4,
+            wrap: false,
+        }
+    }
+}
+
+impl std::str::FromStr for Rule {
+    type Err = anyhow::Error;
+
+    /// Comma separated changes to the puzzle's rule: `rolls=<chars>`, `neighbourhood=<name or
+    /// offsets>`, `threshold=<n>` and `wrap`, e.g. `threshold=3,wrap`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut rule = Rule::default();
+
+        for setting in value.split(',') {
+            match setting.split_once('=') {
+                Some(("rolls", rolls)) if !rolls.is_empty() => rule.rolls = rolls.chars().collect(),
+                Some(("neighbourhood", neighbourhood)) => {
+                    rule.neighbourhood = neighbourhood.parse()?
                 }
-            })
+                Some(("threshold", threshold)) => {
+                    rule.threshold = threshold.parse().map_err(|_| {
+                        anyhow::format_err!("expected a threshold, got {}", threshold)
+                    })?
+                }
+                None if setting == "wrap" => rule.wrap = true,
+                _ => anyhow::bail!(
+                    "expected rolls=<chars>, neighbourhood=<neighbourhood>, threshold=<n> or wrap, \
+                     got {}",
+                    setting
+                ),
+            }
+        }
+
+        Ok(rule)
+    }
+}
+
+impl Rule {
+    pub fn is_roll(&self, cell: char) -> bool {
+        self.rolls.contains(&cell)
+    }
+
+    fn cells_at<'a>(
+        &self,
+        grid: &Grid<char>,
+        position: Position,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + use<'a> {
+        if self.wrap {
+            Either::Left(grid.neighbours_wrapping(position, offsets))
+        } else {
+            Either::Right(grid.neighbours(position, offsets))
+        }
+    }
+
+    fn adjacent_rolls(&self, grid: &Grid<char>, position: Position) -> usize {
+        self.cells_at(grid, position, self.neighbourhood.offsets())
+            .filter(|&neighbour| self.is_roll(grid[neighbour]))
+            .count()
+    }
+}
+
+pub fn accessible_rolls_of_paper<'a>(
+    grid: &'a Grid<char>,
+    rule: &'a Rule,
+) -> impl Iterator<Item = Position> + 'a {
+    grid.iter().filter_map(|(position, &cell)| {
+        (rule.is_roll(cell) && rule.adjacent_rolls(grid, position) < rule.threshold)
+            .then_some(position)
     })
 }
 
-fn accessible_rolls_of_paper_with_removals(original_grid: &[Vec<char>]) -> usize {
+/// Counts the rolls removed round by round.
+pub fn accessible_rolls_of_paper_with_removals(grid: &Grid<char>, rule: &Rule) -> usize {
     let mut counter = 0;
-    let mut grid: Vec<Vec<char>> = original_grid.to_vec();
 
-    loop {
-        let removals: HashSet<(usize, usize)> = accessible_rolls_of_paper(&grid).collect();
+    remove_rolls(grid, rule, |_, _| counter += 1);
+
+    counter
+}
+
+/// The round each roll of paper is removed in, counting from 1, or `None` for rolls (and empty
+/// spaces) that are never removed.
+pub fn removal_rounds(grid: &Grid<char>, rule: &Rule) -> Grid<Option<usize>> {
+    let mut rounds = grid.map(|_| None);
+
+    remove_rolls(grid, rule, |position, round| rounds[position] = Some(round));
+
+    rounds
+}
+
+/// Removes accessible rolls round by round until none are left, calling `removed` with each
+/// roll's position and the round it's removed in.
+///
+/// Only the neighbours of rolls removed in one round can become accessible in the next, so
+/// rather than rescanning the grid each round this keeps a count of each roll's neighbours and
+/// re-examines just the rolls next to those removed.
+///
+/// The cells are laid out in a flat vector with a margin around the grid as wide as the furthest
+/// offset, so each neighbour is a fixed distance along the vector from any cell in the grid. A
+/// margin cell stands in for the cell it wraps around to, or when not wrapping for itself, which
+/// is never a roll.
+fn remove_rolls(grid: &Grid<char>, rule: &Rule, mut removed: impl FnMut(Position, usize)) {
+    let (width, height) = (grid.width(), grid.height());
+
+    if grid.is_empty() {
+        return;
+    }
+
+    // an offset reaching right across the grid either never lands in it or, when wrapping,
+    // lands where the offset brought within half the grid does
+    let within = |offset: isize, size: usize| {
+        if rule.wrap {
+            let offset = offset.rem_euclid(size as isize);
+
+            Some(if offset > size as isize / 2 {
+                offset - size as isize
+            } else {
+                offset
+            })
+        } else {
+            Some(offset).filter(|offset| offset.unsigned_abs() < size)
+        }
+    };
+    let offsets: Vec<(isize, isize)> = rule
+        .neighbourhood
+        .offsets()
+        .iter()
+        .filter_map(|&(row, column)| Some((within(row, height)?, within(column, width)?)))
+        .collect();
+
+    let margin = offsets
+        .iter()
+        .map(|&(row, column)| row.unsigned_abs().max(column.unsigned_abs()))
+        .max()
+        .unwrap_or(0);
+    let stride = width + 2 * margin;
+    let index = |(row, column): Position| (row + margin) * stride + column + margin;
+    let position = |index: usize| (index / stride - margin, index % stride - margin);
 
-        if removals.is_empty() {
-            break;
+    let steps: Vec<isize> = offsets
+        .iter()
+        .map(|&(row, column)| row * stride as isize + column)
+        .collect();
+    let cells = stride * (height + 2 * margin);
+    let wrapped = |cell: usize| {
+        if rule.wrap {
+            let row = (cell / stride) as isize - margin as isize;
+            let column = (cell % stride) as isize - margin as isize;
+
+            index((
+                row.rem_euclid(height as isize) as usize,
+                column.rem_euclid(width as isize) as usize,
+            ))
+        } else {
+            cell
         }
+    };
+
+    // each margin cell starts as a copy of the one it stands in for, so every roll's neighbours
+    // can first be counted a whole offset at a time along the vector
+    let mut remaining = vec![false; cells];
+
+    for row in 0..height {
+        for (remaining, &cell) in remaining[index((row, 0))..].iter_mut().zip(grid.row(row)) {
+            *remaining = rule.is_roll(cell);
+        }
+    }
+
+    if rule.wrap {
+        for cell in 0..cells {
+            remaining[cell] = remaining[wrapped(cell)];
+        }
+    }
+
+    let first = index((0, 0));
+    let mut neighbours = vec![0u32; cells];
 
-        counter += removals.len();
+    for &step in &steps {
+        let from = first.wrapping_add_signed(step);
 
-        for row_index in 0..grid.len() {
-            for column_index in 0..grid[0].len() {
-                if removals.contains(&(row_index, column_index)) {
-                    grid[row_index][column_index] = '.'
+        for (count, &roll) in neighbours[first..].iter_mut().zip(&remaining[from..]) {
+            *count += u32::from(roll);
+        }
+    }
+
+    let mut removals: Vec<usize> = grid
+        .positions()
+        .map(index)
+        .filter(|&cell| remaining[cell] && (neighbours[cell] as usize) < rule.threshold)
+        .collect();
+
+    let mut round = 1;
+
+    while !removals.is_empty() {
+        for &cell in &removals {
+            removed(position(cell), round);
+            remaining[cell] = false;
+        }
+
+        let mut next = Vec::new();
+
+        for cell in removals {
+            // the cells that have the removed roll as a neighbour, which for a lopsided custom
+            // neighbourhood aren't the removed roll's own neighbours
+            for &step in &steps {
+                let neighbour = wrapped(cell.wrapping_add_signed(-step));
+
+                if !remaining[neighbour] {
+                    continue;
+                }
+
+                neighbours[neighbour] -= 1;
+
+                // only the decrement crossing the threshold queues the roll, so it is queued once
+                if neighbours[neighbour] as usize + 1 == rule.threshold {
+                    next.push(neighbour);
                 }
             }
         }
+
+        removals = next;
+        round += 1;
     }
+}
 
-    counter
+/// The answer to `part` when rolls are accessible by `rule` rather than the puzzle's rule.
+pub fn answer(grid: &Grid<char>, rule: &Rule, part: Part) -> usize {
+    match part {
+        Part::One => accessible_rolls_of_paper(grid, rule).count(),
+        Part::Two => accessible_rolls_of_paper_with_removals(grid, rule),
+    }
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let grid = parse(input);
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input<'a> = Grid<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(parse(input)?)
+    }
 
-    println!("part1: {}", accessible_rolls_of_paper(&grid).count());
-    println!("part2: {}", accessible_rolls_of_paper_with_removals(&grid));
+    fn part1(grid: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        Ok(answer(grid, &Rule::default(), Part::One))
+    }
 
-    Ok(())
+    fn part2(grid: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        Ok(answer(grid, &Rule::default(), Part::Two))
+    }
 }
 
 #[cfg(test)]
@@ -94,35 +337,212 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = "..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@."
-            .to_string();
+        let input = include_str!("../../../examples/day4/example.txt").to_string();
 
-        assert_eq!(accessible_rolls_of_paper(&parse(input)).count(), 13)
+        assert_eq!(
+            accessible_rolls_of_paper(&parse(&input).unwrap(), &Rule::default()).count(),
+            13
+        )
     }
 
     #[test]
     fn test_part2() {
-        let input = "..@@.@@@@.
-@@@.@.@.@@
-@@@@@.@.@@
-@.@@@@..@.
-@@.@@@@.@@
-.@@@@@@@.@
-.@.@.@.@@@
-@.@@@.@@@@
-.@@@@@@@@.
-@.@.@@@.@."
-            .to_string();
-
-        assert_eq!(accessible_rolls_of_paper_with_removals(&parse(input)), 43)
+        let input = include_str!("../../../examples/day4/example.txt").to_string();
+
+        assert_eq!(
+            accessible_rolls_of_paper_with_removals(&parse(&input).unwrap(), &Rule::default()),
+            43
+        )
+    }
+
+    #[test]
+    fn test_removal_rounds() {
+        let input = include_str!("../../../examples/day4/example.txt").to_string();
+        let grid = parse(&input).unwrap();
+
+        let rounds = removal_rounds(&grid, &Rule::default());
+
+        let removed_per_round: Vec<usize> = (1..=9)
+            .map(|round| {
+                rounds
+                    .iter()
+                    .filter(|&(_, &removed)| removed == Some(round))
+                    .count()
+            })
+            .collect();
+
+        assert_eq!(removed_per_round, vec![13, 12, 7, 5, 2, 1, 1, 1, 1]);
+        assert_eq!(
+            rounds.iter().filter(|(_, round)| round.is_some()).count(),
+            accessible_rolls_of_paper_with_removals(&grid, &Rule::default())
+        );
+        assert!(
+            accessible_rolls_of_paper(&grid, &Rule::default())
+                .all(|position| rounds[position] == Some(1))
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let grid = parse("@#.\n@@#\n..@").unwrap();
+
+        let accessible =
+            |rule: &Rule| -> Vec<Position> { accessible_rolls_of_paper(&grid, rule).collect() };
+
+        assert_eq!(
+            accessible(&Rule::default()),
+            vec![(0, 0), (1, 0), (1, 1), (2, 2)]
+        );
+
+        let rolls = Rule {
+            rolls: vec!['@', '#'],
+            ..Rule::default()
+        };
+        assert_eq!(accessible(&rolls), vec![(0, 0), (1, 0), (1, 2), (2, 2)]);
+
+        let von_neumann = Rule {
+            neighbourhood: Neighbourhood::VonNeumann,
+            threshold: 2,
+            ..Rule::default()
+        };
+        assert_eq!(accessible(&von_neumann), vec![(0, 0), (1, 1), (2, 2)]);
+
+        // only the cell directly above counts, so each roll in a column waits on the one above
+        let above = Rule {
+            neighbourhood: Neighbourhood::Custom(vec![(-1, 0)]),
+            threshold: 1,
+            ..Rule::default()
+        };
+        assert_eq!(accessible(&above), vec![(0, 0), (1, 1), (2, 2)]);
+        assert_eq!(
+            removal_rounds(&grid, &above)[(1, 0)],
+            Some(2),
+            "removed once the roll above it is"
+        );
+
+        let fewer = Rule {
+            threshold: 3,
+            ..Rule::default()
+        };
+        assert_eq!(accessible(&fewer), vec![(0, 0), (1, 0), (2, 2)]);
+
+        // on a 3x3 torus every other cell is a neighbour
+        let wrapping = Rule {
+            wrap: true,
+            ..fewer
+        };
+        assert_eq!(accessible(&wrapping), vec![]);
+    }
+
+    #[test]
+    fn test_removal_rounds_with_rules_match_rescanning() {
+        let input = include_str!("../../../examples/day4/example.txt").to_string();
+        let grid = parse(&input).unwrap();
+
+        for rule in [
+            Rule::default(),
+            Rule {
+                rolls: vec!['@', '#'],
+                threshold: 3,
+                ..Rule::default()
+            },
+            Rule {
+                neighbourhood: Neighbourhood::VonNeumann,
+                threshold: 3,
+                wrap: true,
+                ..Rule::default()
+            },
+            Rule {
+                neighbourhood: Neighbourhood::Custom(vec![(-1, -1), (0, 2), (2, 1)]),
+                threshold: 2,
+                ..Rule::default()
+            },
+            // reaching right across the 10x10 grid, and when wrapping back into it
+            Rule {
+                neighbourhood: Neighbourhood::Custom(vec![(0, 12), (-4, 1), (5, -5)]),
+                threshold: 1,
+                ..Rule::default()
+            },
+            Rule {
+                neighbourhood: Neighbourhood::Custom(vec![(0, 12), (-14, 1), (5, -5), (0, 0)]),
+                threshold: 3,
+                wrap: true,
+                ..Rule::default()
+            },
+        ] {
+            let rounds = removal_rounds(&grid, &rule);
+            let mut remaining = grid.clone();
+            let mut round = 1;
+
+            loop {
+                let removals: Vec<Position> =
+                    accessible_rolls_of_paper(&remaining, &rule).collect();
+
+                if removals.is_empty() {
+                    break;
+                }
+
+                for position in removals {
+                    assert_eq!(rounds[position], Some(round), "{:?}", rule);
+                    remaining[position] = '.';
+                }
+
+                round += 1;
+            }
+
+            assert!(rounds.iter().all(
+                |(position, round)| round.is_some() == (remaining[position] != grid[position])
+            ));
+            assert_eq!(
+                accessible_rolls_of_paper_with_removals(&grid, &rule),
+                rounds.iter().filter(|(_, round)| round.is_some()).count(),
+                "{:?}",
+                rule
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        assert_eq!("threshold=4".parse::<Rule>().unwrap(), Rule::default());
+        assert_eq!(
+            "rolls=@#,neighbourhood=von-neumann,threshold=3,wrap"
+                .parse::<Rule>()
+                .unwrap(),
+            Rule {
+                rolls: vec!['@', '#'],
+                neighbourhood: Neighbourhood::VonNeumann,
+                threshold: 3,
+                wrap: true,
+            }
+        );
+        assert_eq!(
+            "neighbourhood=-1:0;0:2"
+                .parse::<Rule>()
+                .unwrap()
+                .neighbourhood,
+            Neighbourhood::Custom(vec![(-1, 0), (0, 2)])
+        );
+        assert_eq!(
+            "threshold=x".parse::<Rule>().unwrap_err().to_string(),
+            "expected a threshold, got x"
+        );
+        assert_eq!(
+            "neighbourhood=hex".parse::<Rule>().unwrap_err().to_string(),
+            "expected neighbourhood of moore, von-neumann or row:column offsets, got hex"
+        );
+        assert_eq!(
+            "rolls=".parse::<Rule>().unwrap_err().to_string(),
+            "expected rolls=<chars>, neighbourhood=<neighbourhood>, threshold=<n> or wrap, \
+             got rolls="
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("..@\n.@\n@@.").unwrap_err().to_string(),
+            "line 2, column 1: expected row to be 3 wide like the first, found \".@\""
+        );
     }
 }
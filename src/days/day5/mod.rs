@@ -1,140 +1,93 @@
-use std::{collections::HashSet, num::ParseIntError, ops::RangeInclusive};
-
-fn parse_fresh_ingredient_ranges(input: &str) -> anyhow::Result<Vec<RangeInclusive<usize>>> {
-    input
-        .lines()
-        .map(|line| {
-            let range = line
-                .split_once('-')
-                .ok_or(anyhow::format_err!("expected - separated ingredient IDs"))?;
-
-            let start: usize = range.0.parse()?;
-            let end: usize = range.1.parse()?;
-
-            Ok(start..=end)
-        })
-        .collect()
+use std::ops::RangeInclusive;
+
+use crate::{
+    interval_set::IntervalSet,
+    parse::{Line, ParseError, lines},
+    solution::Solution,
+};
+
+fn parse_fresh_ingredient_range(line: Line) -> Result<RangeInclusive<usize>, ParseError> {
+    let (start, end) = line
+        .text
+        .split_once('-')
+        .ok_or(line.error(line.text, "expected - separated ingredient IDs"))?;
+
+    let start: usize = line.parse(start, "a start ingredient ID")?;
+    let end: usize = line.parse(end, "an end ingredient ID")?;
+
+    Ok(start..=end)
 }
 
-fn parse_available_ingredients(input: &str) -> Result<Vec<usize>, ParseIntError> {
-    input.lines().map(str::parse::<usize>).collect()
+pub fn parse_fresh_ingredient_ranges(input: &str) -> Result<IntervalSet, ParseError> {
+    lines(input).map(parse_fresh_ingredient_range).collect()
 }
 
-fn parse(input: String) -> anyhow::Result<(Vec<RangeInclusive<usize>>, Vec<usize>)> {
-    let parts = input.split_once("\n\n").ok_or(anyhow::format_err!(
-        "expected fresh ingredient ID ranges separated by blank line, then available ingredient IDs"
-    ))?;
+pub fn parse(input: &str) -> Result<(IntervalSet, Vec<usize>), ParseError> {
+    let lines: Vec<Line> = lines(input).collect();
 
-    let fresh_ingredients = parse_fresh_ingredient_ranges(parts.0)?;
+    let separator = lines
+        .iter()
+        .position(|line| line.text.is_empty())
+        .ok_or(ParseError::new(
+            lines.len() + 1,
+            1,
+            "",
+            "expected a blank line between fresh ingredient ID ranges and available ingredient IDs",
+        ))?;
+
+    let fresh_ingredients = lines[..separator]
+        .iter()
+        .copied()
+        .map(parse_fresh_ingredient_range)
+        .collect::<Result<_, _>>()?;
 
-    let available_ingredients = parse_available_ingredients(parts.1)?;
+    let available_ingredients = lines[separator + 1..]
+        .iter()
+        .map(|line| line.parse(line.text, "an available ingredient ID"))
+        .collect::<Result<_, _>>()?;
 
     Ok((fresh_ingredients, available_ingredients))
 }
 
-fn fresh_available_ingredients(
-    fresh_ingredient_ranges: &[RangeInclusive<usize>],
+pub fn fresh_available_ingredients(
+    fresh_ingredients: &IntervalSet,
     available_ingredients: &[usize],
 ) -> usize {
     available_ingredients
         .iter()
-        .filter(|ingredient_id| {
-            fresh_ingredient_ranges
-                .iter()
-                .any(|fresh_ingredient_range| fresh_ingredient_range.contains(ingredient_id))
-        })
+        .filter(|&&ingredient_id| fresh_ingredients.contains(ingredient_id))
         .count()
 }
 
-fn size(range: &RangeInclusive<usize>) -> usize {
-    range.end() - range.start() + 1
+pub fn total_fresh_ingredients(fresh_ingredients: &IntervalSet) -> u128 {
+    fresh_ingredients.len()
 }
 
-fn is_contained_by(subset: &RangeInclusive<usize>, superset: &RangeInclusive<usize>) -> bool {
-    superset.start() <= subset.start() && superset.end() >= subset.end()
-}
+pub struct Day5;
 
-fn total_fresh_ingredients(fresh_ingredient_ranges: &[RangeInclusive<usize>]) -> usize {
-    let mut fresh_ingredient_ranges: Vec<RangeInclusive<usize>> = fresh_ingredient_ranges.to_vec();
-
-    let mut total = 0;
-    let mut skip: HashSet<usize> = HashSet::new();
-
-    for i in 0..fresh_ingredient_ranges.len() {
-        let range = fresh_ingredient_ranges[i].clone();
-
-        if skip.contains(&i) {
-            continue;
-        }
-
-        let mut subtotal = size(&range);
-
-        for j in i + 1..fresh_ingredient_ranges.len() {
-            if skip.contains(&j) {
-                continue;
-            }
-
-            let other = &fresh_ingredient_ranges[j];
-
-            // self:   2--6
-            // other: 1----10
-            // skip self to avoid double counting later
-            if is_contained_by(&range, other) {
-                skip.insert(i);
-                subtotal = 0;
-                break;
-            }
-
-            // self: 1------10
-            // other:  2--6
-            // skip other to avoid double counting later
-            if is_contained_by(other, &range) {
-                skip.insert(j);
-                continue;
-            }
-
-            if other.start() > range.end() || other.end() < range.start() {
-                continue;
-            }
-            // self:    5----10
-            // other:       8---11
-            else if range.start() < other.start() && range.end() < other.end() {
-                fresh_ingredient_ranges[j] = RangeInclusive::new(range.end() + 1, *other.end());
-
-                if fresh_ingredient_ranges[j].is_empty() {
-                    skip.insert(j);
-                }
-            // self:     5----10
-            // other:  3---6
-            } else if other.start() < range.start() && other.end() < range.end() {
-                fresh_ingredient_ranges[j] = RangeInclusive::new(*other.start(), range.start() - 1);
-
-                if fresh_ingredient_ranges[j].is_empty() {
-                    skip.insert(j);
-                }
-            }
-        }
-
-        total += subtotal;
-    }
-
-    total
-}
+impl Solution for Day5 {
+    const DAY: u8 = 5;
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let (fresh_ingredient_ranges, available_ingredients) = parse(input)?;
+    type Input<'a> = (IntervalSet, Vec<usize>);
+    type Part1 = usize;
+    type Part2 = u128;
 
-    println!(
-        "part1: {}",
-        fresh_available_ingredients(&fresh_ingredient_ranges, &available_ingredients)
-    );
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(parse(input)?)
+    }
 
-    println!(
-        "part2: {}",
-        total_fresh_ingredients(&fresh_ingredient_ranges)
-    );
+    fn part1(
+        (fresh_ingredients, available_ingredients): &Self::Input<'_>,
+    ) -> anyhow::Result<Self::Part1> {
+        Ok(fresh_available_ingredients(
+            fresh_ingredients,
+            available_ingredients,
+        ))
+    }
 
-    Ok(())
+    fn part2((fresh_ingredients, _): &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        Ok(total_fresh_ingredients(fresh_ingredients))
+    }
 }
 
 #[cfg(test)]
@@ -145,20 +98,9 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = "3-5
-10-14
-16-20
-12-18
+        let input = include_str!("../../../examples/day5/example.txt").to_string();
 
-1
-5
-8
-11
-17
-32"
-        .to_string();
-
-        let (fresh_ranges, available) = parse(input).unwrap();
+        let (fresh_ranges, available) = parse(&input).unwrap();
 
         assert_eq!(fresh_available_ingredients(&fresh_ranges, &available), 3);
     }
@@ -176,4 +118,24 @@ mod tests {
             14
         );
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("3-5\n10-14").unwrap_err().to_string(),
+            "line 3, column 1: expected a blank line between fresh ingredient ID ranges and available ingredient IDs, found end of line"
+        );
+        assert_eq!(
+            parse("3-5\n10:14\n\n1").unwrap_err().to_string(),
+            "line 2, column 1: expected - separated ingredient IDs, found \"10:14\""
+        );
+        assert_eq!(
+            parse("3-5\n10-x\n\n1").unwrap_err().to_string(),
+            "line 2, column 4: expected an end ingredient ID, found \"x\""
+        );
+        assert_eq!(
+            parse("3-5\n\n1\n-2").unwrap_err().to_string(),
+            "line 4, column 1: expected an available ingredient ID, found \"-2\""
+        );
+    }
 }
@@ -1,33 +1,70 @@
-use std::num::ParseIntError;
+use crate::{
+    parse::{Line, ParseError, lines},
+    solution::Solution,
+};
 
-fn calculate_worksheet(numbers: &[Vec<usize>], operators: &[&str]) -> usize {
+pub fn calculate_worksheet(numbers: &[Vec<usize>], operators: &[&str]) -> usize {
     operators
         .iter()
         .zip(numbers)
         .map(|(&operator, problem)| match operator {
             "+" => problem.iter().sum::<usize>(),
             "*" => problem.iter().product::<usize>(),
-            _ => unimplemented!(),
+            _ => unreachable!("operators are validated when parsing"),
         })
         .sum()
 }
-fn parse_operators(line: &str) -> Vec<&str> {
-    line.split_whitespace().collect()
+
+/// A worksheet's lines of numbers, its line of operators, and the operator of each problem.
+type Worksheet<'a> = (Vec<Line<'a>>, Line<'a>, Vec<&'a str>);
+
+/// Splits the worksheet into its lines of numbers and operators.
+fn parse_worksheet(input: &str) -> Result<Worksheet<'_>, ParseError> {
+    let mut lines: Vec<Line> = lines(input).collect();
+
+    let operator_line = lines
+        .pop()
+        .ok_or(ParseError::new(1, 1, "", "expected a worksheet"))?;
+
+    let operators = operator_line
+        .text
+        .split_whitespace()
+        .map(|operator| match operator {
+            "+" | "*" => Ok(operator),
+            _ => Err(operator_line.error(operator, "expected an operator of + or *")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((lines, operator_line, operators))
 }
 
-fn parse_as_cephalopod_math(input: &str) -> anyhow::Result<(Vec<Vec<usize>>, Vec<&str>)> {
-    let lines: Vec<&str> = input.lines().collect();
+pub fn parse_as_cephalopod_math(input: &str) -> Result<(Vec<Vec<usize>>, Vec<&str>), ParseError> {
+    let (lines, operator_line, operators) = parse_worksheet(input)?;
 
-    let inputs_per_problem = lines.len() - 1;
+    let problems = operators.len();
 
-    let problems = lines[0].split_whitespace().count();
+    if problems == 0 {
+        return Ok((Vec::new(), operators));
+    }
 
     let mut numbers: Vec<Vec<usize>> = vec![vec![0; 0]; problems];
 
+    for line in &lines {
+        if let Some((index, c)) = line
+            .text
+            .char_indices()
+            .find(|&(_, c)| !c.is_ascii_digit() && c != ' ')
+        {
+            return Err(line.error(
+                &line.text[index..index + c.len_utf8()],
+                "expected a digit or space",
+            ));
+        }
+    }
+
     let mut numbers_as_bytes: Vec<Vec<u8>> = lines
         .iter()
-        .take(inputs_per_problem)
-        .map(|&line| line.as_bytes().to_vec())
+        .map(|line| line.text.as_bytes().to_vec())
         .collect();
 
     let total_characters = numbers_as_bytes
@@ -45,63 +82,77 @@ fn parse_as_cephalopod_math(input: &str) -> anyhow::Result<(Vec<Vec<usize>>, Vec
         }
     });
 
-    let mut i = total_characters - 1;
+    if total_characters == 0 {
+        return Err(ParseError::new(
+            lines.len() + 1,
+            1,
+            "",
+            "expected numbers above the operators",
+        ));
+    }
+
     let mut problem = problems - 1;
 
-    loop {
-        let input: Vec<u8> = numbers_as_bytes
+    for i in (0..total_characters).rev() {
+        let digits: Vec<(usize, u8)> = numbers_as_bytes
             .iter()
-            .filter_map(|line| {
-                if line[i] >= 49 && line[i] <= 57 {
-                    Some(line[i] - b'0')
-                } else {
-                    None
-                }
-            })
+            .enumerate()
+            .filter(|(_, line)| line[i].is_ascii_digit())
+            .map(|(row, line)| (row, line[i] - b'0'))
             .collect();
 
-        if input.is_empty() {
-            problem -= 1;
-        } else {
-            numbers[problem].push(
-                input
-                    .iter()
-                    .rev()
-                    .enumerate()
-                    .fold(0usize, |acc, (digit, &value)| {
-                        acc + 10usize.pow(digit as u32) * value as usize
-                    }),
-            );
-        }
+        let Some(&(top, _)) = digits.first() else {
+            // a blank column separates problems, so there's one more problem than operators
+            problem = problem.checked_sub(1).ok_or(operator_line.error(
+                operator_line.text.get(i..i + 1).unwrap_or(""),
+                "expected an operator below each problem",
+            ))?;
 
-        if i != 0 {
-            i -= 1;
-        } else {
-            break;
-        }
-    }
+            continue;
+        };
+
+        let number = digits.iter().try_fold(0usize, |number, &(_, digit)| {
+            number.checked_mul(10)?.checked_add(digit as usize)
+        });
 
-    let operators = parse_operators(lines[lines.len() - 1]);
+        let number = number.ok_or(lines[top].error(
+            &lines[top].text[i..i + 1],
+            "expected a number small enough to add up",
+        ))?;
+
+        numbers[problem].push(number);
+    }
 
     Ok((numbers, operators))
 }
 
-fn parse(input: &str) -> anyhow::Result<(Vec<Vec<usize>>, Vec<&str>)> {
-    let lines: Vec<&str> = input.lines().collect();
-
-    let operators = parse_operators(lines[lines.len() - 1]);
+pub fn parse(input: &str) -> Result<(Vec<Vec<usize>>, Vec<&str>), ParseError> {
+    let (lines, _, operators) = parse_worksheet(input)?;
 
     let number_of_problems = operators.len();
 
     let numbers = lines
         .iter()
-        .take(lines.len() - 1)
         .map(|line| {
-            line.split_whitespace()
-                .map(str::parse::<usize>)
-                .collect::<Result<Vec<_>, ParseIntError>>()
+            let numbers = line
+                .text
+                .split_whitespace()
+                .map(|number| line.parse(number, "a number"))
+                .collect::<Result<Vec<usize>, _>>()?;
+
+            if numbers.len() != number_of_problems {
+                return Err(line.error(
+                    line.text,
+                    format!(
+                        "expected a number for each of {} problems",
+                        number_of_problems
+                    ),
+                ));
+            }
+
+            Ok(numbers)
         })
-        .collect::<Result<Vec<Vec<usize>>, ParseIntError>>()?;
+        .collect::<Result<Vec<Vec<usize>>, _>>()?;
 
     let numbers: Vec<Vec<usize>> = (0..number_of_problems)
         .map(|i| numbers.iter().map(|line| line[i]).collect())
@@ -110,16 +161,31 @@ fn parse(input: &str) -> anyhow::Result<(Vec<Vec<usize>>, Vec<&str>)> {
     Ok((numbers, operators))
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let (numbers, operators) = parse(&input)?;
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
 
-    println!("part1: {}", calculate_worksheet(&numbers, &operators));
+    // each part reads the worksheet differently, so parsing is deferred to the parts
+    type Input<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let (numbers, operators) = parse_as_cephalopod_math(&input)?;
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        let (numbers, operators) = parse(input)?;
+
+        Ok(calculate_worksheet(&numbers, &operators))
+    }
 
-    println!("part2: {}", calculate_worksheet(&numbers, &operators));
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        let (numbers, operators) = parse_as_cephalopod_math(input)?;
 
-    Ok(())
+        Ok(calculate_worksheet(&numbers, &operators))
+    }
 }
 
 #[cfg(test)]
@@ -128,11 +194,7 @@ mod tests {
 
     #[test]
     fn test_calculate_worksheet() {
-        let input = "123 328  51 64
- 45 64  387 23
-  6 98  215 314
-*   +   *   +  "
-            .to_string();
+        let input = include_str!("../../../examples/day6/example.txt").to_string();
 
         let (numbers, operators) = parse(&input).unwrap();
 
@@ -141,15 +203,63 @@ mod tests {
 
     #[test]
     fn test_calculate_worksheet_with_cephalopod_math() {
-        // need to break up the input to prevent editor from trimming (in this case meaningful) whitespace
-        let input = "123 328  51 64
- 45 64  387 23
-  6 98  215 314
-*   +   *   +  "
-            .to_string();
+        let input = include_str!("../../../examples/day6/example.txt").to_string();
 
         let (numbers, operators) = parse_as_cephalopod_math(&input).unwrap();
 
         assert_eq!(calculate_worksheet(&numbers, &operators), 3263827);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("").unwrap_err().to_string(),
+            "line 1, column 1: expected a worksheet, found end of line"
+        );
+        assert_eq!(
+            parse("1 2\n+ -").unwrap_err().to_string(),
+            "line 2, column 3: expected an operator of + or *, found \"-\""
+        );
+        assert_eq!(
+            parse("1 2\n3\n+ *").unwrap_err().to_string(),
+            "line 2, column 1: expected a number for each of 2 problems, found \"3\""
+        );
+        assert_eq!(
+            parse("1 x\n+ *").unwrap_err().to_string(),
+            "line 1, column 3: expected a number, found \"x\""
+        );
+        assert_eq!(
+            parse_as_cephalopod_math("1 2 3\n4 5 6\n+ *")
+                .unwrap_err()
+                .to_string(),
+            "line 3, column 2: expected an operator below each problem, found \" \""
+        );
+        assert_eq!(
+            parse_as_cephalopod_math("1 2 3\n+")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 2: expected an operator below each problem, found end of line"
+        );
+        assert!(parse_as_cephalopod_math("\n+").is_err());
+        assert_eq!(
+            parse_as_cephalopod_math("1 2\n3 x\n+ *")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 3: expected a digit or space, found \"x\""
+        );
+        assert_eq!(
+            parse_as_cephalopod_math(&format!("{}+", "9\n".repeat(25)))
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 1: expected a number small enough to add up, found \"9\""
+        );
+    }
+
+    #[test]
+    fn test_cephalopod_math_with_zeros() {
+        assert_eq!(
+            parse_as_cephalopod_math("10 20\n+ *").unwrap(),
+            (vec![vec![0, 1], vec![0, 2]], vec!["+", "*"])
+        );
+    }
 }
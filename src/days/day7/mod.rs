@@ -1,11 +1,20 @@
 use std::collections::{HashMap, HashSet, hash_map::Entry};
 
-fn parse(input: String) -> Vec<Vec<u8>> {
-    input.lines().map(|line| line.as_bytes().to_vec()).collect()
+use crate::{grid::Grid, parse::ParseError, solution::Solution};
+
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let manifold = Grid::parse(input, |c| u8::try_from(c).ok(), "a tachyon manifold cell")?;
+
+    if manifold.is_empty() {
+        return Err(ParseError::new(1, 1, "", "expected a tachyon manifold"));
+    }
+
+    Ok(manifold)
 }
 
-fn find_beam_entrypoint(manifold: &[Vec<u8>]) -> anyhow::Result<usize> {
-    let column = manifold[0]
+fn find_beam_entrypoint(manifold: &Grid<u8>) -> anyhow::Result<usize> {
+    let column = manifold
+        .row(0)
         .iter()
         .position(|&x| x == b'S')
         .ok_or(anyhow::format_err!(
@@ -15,14 +24,14 @@ fn find_beam_entrypoint(manifold: &[Vec<u8>]) -> anyhow::Result<usize> {
     Ok(column)
 }
 
-fn beam_splits(manifold: &[Vec<u8>]) -> anyhow::Result<usize> {
+pub fn beam_splits(manifold: &Grid<u8>) -> anyhow::Result<usize> {
     let entrypoint = find_beam_entrypoint(manifold)?;
 
     let mut beams = HashSet::from([entrypoint]);
 
     let mut splits = 0;
 
-    for row in manifold.iter().skip(1) {
+    for row in manifold.rows().skip(1) {
         let mut next = beams.clone();
 
         for &beam in beams.iter() {
@@ -45,45 +54,27 @@ fn beam_splits(manifold: &[Vec<u8>]) -> anyhow::Result<usize> {
     Ok(splits)
 }
 
-fn quantum_tachyon_timelines(manifold: &[Vec<u8>]) -> anyhow::Result<usize> {
-    // let entrypoint = find_beam_entrypoint(manifold)?;
-
-    // let mut stack = vec![(0usize, entrypoint)];
-    // let mut timelines = 0;
-
-    // let rows = manifold.len() - 1;
-
-    // let mut split = 0;
-
-    // while let Some((row, column)) = stack.pop() {
-    //     if manifold[row][column] == b'^' {
-    //         split += 1;
-    //         stack.push((row + 1, column - 1));
-    //         stack.push((row + 1, column + 1));
-    //     } else if row >= rows {
-    //         timelines += 1;
-    //     } else {
-    //         stack.push((row + 1, column));
-    //     }
-    // }
-
-    // println!("{}", split);
-
-    // Ok(timelines)
+pub fn quantum_tachyon_timelines(manifold: &Grid<u8>) -> anyhow::Result<usize> {
     let entrypoint = find_beam_entrypoint(manifold)?;
 
     let mut beams = HashMap::from([(entrypoint, 1usize)]);
+    // a particle split off the side of the manifold leaves it, ending its timeline there
+    let mut exited = 0;
 
-    for row in manifold.iter().skip(1) {
+    for row in manifold.rows().skip(1) {
         let mut next = beams.clone();
 
-        for (&beam, timelines) in beams.iter() {
+        for (&beam, &timelines) in beams.iter() {
             if row[beam] == b'^' {
-                *next.entry(beam + 1).or_insert(0) += timelines;
-                *next.entry(beam - 1).or_insert(0) += timelines;
+                for side in [beam.checked_sub(1), Some(beam + 1)] {
+                    match side.filter(|&side| side < row.len()) {
+                        Some(side) => *next.entry(side).or_insert(0) += timelines,
+                        None => exited += timelines,
+                    }
+                }
 
                 if let Entry::Occupied(mut o) = next.entry(beam) {
-                    if *o.get() == *timelines {
+                    if *o.get() == timelines {
                         o.remove();
                     } else {
                         *o.get_mut() -= timelines;
@@ -95,17 +86,29 @@ fn quantum_tachyon_timelines(manifold: &[Vec<u8>]) -> anyhow::Result<usize> {
         beams = next;
     }
 
-    Ok(beams.values().sum())
+    Ok(beams.values().sum::<usize>() + exited)
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let manifold = parse(input);
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input<'a> = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    println!("part1: {}", beam_splits(&manifold)?);
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(parse(input)?)
+    }
 
-    println!("part2: {}", quantum_tachyon_timelines(&manifold)?);
+    fn part1(manifold: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        beam_splits(manifold)
+    }
 
-    Ok(())
+    fn part2(manifold: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        quantum_tachyon_timelines(manifold)
+    }
 }
 
 #[cfg(test)]
@@ -114,51 +117,44 @@ mod tests {
 
     #[test]
     fn test_beam_splits() {
-        let input = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-..............."
-            .to_string();
-
-        let manifold = parse(input);
+        let input = include_str!("../../../examples/day7/example.txt").to_string();
+
+        let manifold = parse(&input).unwrap();
 
         assert_eq!(beam_splits(&manifold).unwrap(), 21);
     }
 
     #[test]
     fn test_quantum_tachyon_timelines() {
-        let input = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-..............."
-            .to_string();
-
-        let manifold = parse(input);
+        let input = include_str!("../../../examples/day7/example.txt").to_string();
+
+        let manifold = parse(&input).unwrap();
 
         assert_eq!(quantum_tachyon_timelines(&manifold).unwrap(), 40);
     }
+
+    #[test]
+    fn test_splitters_on_the_edges() {
+        let manifold = parse("S..\n...\n^..\n..^").unwrap();
+
+        assert_eq!(beam_splits(&manifold).unwrap(), 1);
+        assert_eq!(quantum_tachyon_timelines(&manifold).unwrap(), 2);
+
+        let manifold = parse("..S\n..^\n.^.").unwrap();
+
+        assert_eq!(beam_splits(&manifold).unwrap(), 2);
+        assert_eq!(quantum_tachyon_timelines(&manifold).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("").unwrap_err().to_string(),
+            "line 1, column 1: expected a tachyon manifold, found end of line"
+        );
+        assert_eq!(
+            parse("..S..\n.....\n..^.").unwrap_err().to_string(),
+            "line 3, column 1: expected row to be 5 wide like the first, found \"..^.\""
+        );
+    }
 }
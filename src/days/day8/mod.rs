@@ -1,34 +1,37 @@
-use std::{collections::HashSet, num::ParseIntError};
+use std::collections::HashSet;
 
-fn parse(input: String) -> anyhow::Result<Vec<Vec<usize>>> {
-    input
-        .lines()
+use crate::{
+    parse::{ParseError, lines},
+    solution::Solution,
+};
+
+pub fn parse(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    lines(input)
         .map(|line| {
             let coordinates = line
+                .text
                 .split(",")
-                .map(str::parse::<usize>)
-                .collect::<Result<Vec<usize>, ParseIntError>>()?;
+                .map(|coordinate| line.parse(coordinate, "a coordinate"))
+                .collect::<Result<Vec<usize>, _>>()?;
 
-            debug_assert_eq!(coordinates.len(), 3);
+            if coordinates.len() != 3 {
+                return Err(line.error(line.text, "expected X,Y,Z coordinates"));
+            }
 
             Ok(coordinates)
         })
         .collect()
 }
 
-fn compute_distances(junction_boxes: &[Vec<usize>]) -> Vec<(usize, usize, f64)> {
+pub fn compute_distances(junction_boxes: &[Vec<usize>]) -> Vec<(usize, usize, f64)> {
     let number_of_junctions = junction_boxes.len();
 
     let mut distances =
         vec![(0, 0, f64::MAX); (number_of_junctions) / 2 * (number_of_junctions - 1)];
     let mut index = 0;
 
-    for i in 0..junction_boxes.len() {
-        let a = &junction_boxes[i];
-
-        for j in i + 1..junction_boxes.len() {
-            let b = &junction_boxes[j];
-
+    for (i, a) in junction_boxes.iter().enumerate() {
+        for (j, b) in junction_boxes.iter().enumerate().skip(i + 1) {
             let distance = (((a[0].abs_diff(b[0])).pow(2)
                 + (a[1].abs_diff(b[1])).pow(2)
                 + (a[2].abs_diff(b[2])).pow(2)) as f64)
@@ -44,7 +47,7 @@ fn compute_distances(junction_boxes: &[Vec<usize>]) -> Vec<(usize, usize, f64)>
     distances
 }
 
-fn three_largest_circuits_after_n_connections(
+pub fn three_largest_circuits_after_n_connections(
     distances: &[(usize, usize, f64)],
     connections: usize,
 ) -> usize {
@@ -85,7 +88,7 @@ fn three_largest_circuits_after_n_connections(
         .product()
 }
 
-fn first_connection_to_form_single_circuit(
+pub fn first_connection_to_form_single_circuit(
     junction_boxes: &[Vec<usize>],
     distances: &[(usize, usize, f64)],
 ) -> [(usize, usize, usize); 2] {
@@ -128,21 +131,32 @@ fn first_connection_to_form_single_circuit(
     unreachable!()
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let junction_boxes = parse(input)?;
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
 
-    let distances = compute_distances(&junction_boxes);
+    type Input<'a> = (Vec<Vec<usize>>, Vec<(usize, usize, f64)>);
+    type Part1 = usize;
+    type Part2 = usize;
 
-    println!(
-        "part1: {}",
-        three_largest_circuits_after_n_connections(&distances, 1000)
-    );
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        let junction_boxes = parse(input)?;
 
-    let [a, b] = first_connection_to_form_single_circuit(&junction_boxes, &distances);
+        let distances = compute_distances(&junction_boxes);
+
+        Ok((junction_boxes, distances))
+    }
+
+    fn part1((_, distances): &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        Ok(three_largest_circuits_after_n_connections(distances, 1000))
+    }
 
-    println!("part2: {}", a.0 * b.0);
+    fn part2((junction_boxes, distances): &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        let [a, b] = first_connection_to_form_single_circuit(junction_boxes, distances);
 
-    Ok(())
+        Ok(a.0 * b.0)
+    }
 }
 
 #[cfg(test)]
@@ -151,31 +165,11 @@ mod tests {
 
     #[test]
     fn test_three_largest_circuits() {
-        let input = "162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689"
-            .to_string();
+        let input = include_str!("../../../examples/day8/example.txt").to_string();
 
         assert_eq!(
             three_largest_circuits_after_n_connections(
-                &compute_distances(&parse(input).unwrap()),
+                &compute_distances(&parse(&input).unwrap()),
                 10
             ),
             40
@@ -184,29 +178,9 @@ mod tests {
 
     #[test]
     fn test_first_connection_to_form_single_circuit() {
-        let input = "162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689"
-            .to_string();
-
-        let junction_boxes = &parse(input).unwrap();
+        let input = include_str!("../../../examples/day8/example.txt").to_string();
+
+        let junction_boxes = &parse(&input).unwrap();
 
         let distances = &compute_distances(junction_boxes);
 
@@ -215,4 +189,16 @@ mod tests {
             [(216, 146, 977), (117, 168, 530)],
         );
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("1,2,3\n4,5").unwrap_err().to_string(),
+            "line 2, column 1: expected X,Y,Z coordinates, found \"4,5\""
+        );
+        assert_eq!(
+            parse("1,b,3").unwrap_err().to_string(),
+            "line 1, column 3: expected a coordinate, found \"b\""
+        );
+    }
 }
@@ -1,24 +1,30 @@
-use std::{
-    cmp::Reverse,
-    collections::{HashSet, VecDeque},
+use std::{cmp::Reverse, collections::VecDeque};
+
+use crate::{
+    grid::{Grid, Position},
+    parse::{ParseError, lines},
+    solution::Solution,
 };
 
-fn parse_red_tile_positions(input: String) -> anyhow::Result<Vec<[usize; 2]>> {
-    input
-        .lines()
-        .map(|line| match line.split_once(",") {
+pub fn parse_red_tile_positions(input: &str) -> Result<Vec<[usize; 2]>, ParseError> {
+    if input.trim().is_empty() {
+        return Err(ParseError::new(1, 1, "", "expected red tile positions"));
+    }
+
+    lines(input)
+        .map(|line| match line.text.split_once(",") {
             Some((left, right)) => {
-                let column: usize = left.parse()?;
-                let row: usize = right.parse()?;
+                let column: usize = line.parse(left, "a column")?;
+                let row: usize = line.parse(right, "a row")?;
 
                 Ok([column, row])
             }
-            None => anyhow::bail!("expected comma separated column,row"),
+            None => Err(line.error(line.text, "expected comma separated column,row")),
         })
         .collect()
 }
 
-fn largest_rectangle_with_red_tile_corners(red_tile_positions: &[[usize; 2]]) -> usize {
+pub fn largest_rectangle_with_red_tile_corners(red_tile_positions: &[[usize; 2]]) -> usize {
     let mut max = usize::MIN;
 
     for (i, a) in red_tile_positions.iter().enumerate() {
@@ -50,103 +56,59 @@ fn areas_by_descending_size(red_tile_positions: &[[usize; 2]]) -> Vec<(usize, us
     areas
 }
 
-#[allow(dead_code)]
-fn print_grid(grid: &[Vec<bool>]) {
-    grid.iter()
-        .map(|row| {
-            row.iter()
-                .map(|cell| match cell {
-                    true => 'X',
-                    false => '.',
-                })
-                .collect::<Vec<char>>()
-        })
-        .for_each(|row| println!("{:?}", row));
-    println!();
-}
-
-fn flood_fill(grid: &mut [Vec<bool>]) {
-    let mut frontier: VecDeque<[usize; 2]> = VecDeque::new();
-    let mut outside: HashSet<[usize; 2]> = HashSet::new();
+fn flood_fill(grid: &mut Grid<bool>) {
+    let mut frontier: VecDeque<Position> = VecDeque::new();
+    let mut outside = Grid::new(grid.width(), grid.height(), false);
 
-    grid[0][0] = false;
-    outside.insert([0, 0]);
-    frontier.push_back([0, 0]);
+    grid[(0, 0)] = false;
+    outside[(0, 0)] = true;
+    frontier.push_back((0, 0));
 
-    while let Some([column, row]) = frontier.pop_front() {
-        let mut neighbours: Vec<[usize; 2]> = Vec::with_capacity(4);
-
-        if row != 0 {
-            neighbours.push([column, row - 1]);
-        }
-        if row < grid.len() - 1 {
-            neighbours.push([column, row + 1])
-        }
-
-        if column != 0 {
-            neighbours.push([column - 1, row])
-        }
-        if column < grid[0].len() - 1 {
-            neighbours.push([column + 1, row])
-        }
-
-        for neighbour in neighbours {
-            let [nc, nr] = neighbour;
-
-            if !grid[nr][nc] && !outside.contains(&neighbour) {
+    while let Some(position) = frontier.pop_front() {
+        for neighbour in grid.neighbours4(position) {
+            if !grid[neighbour] && !outside[neighbour] {
                 frontier.push_back(neighbour);
-                outside.insert(neighbour);
+                outside[neighbour] = true;
             }
         }
     }
 
-    for row in 0..grid.len() {
-        for column in 0..grid[0].len() {
-            let position = [column, row];
-
-            if !outside.contains(&position) {
-                grid[row][column] = true
-            }
+    for position in grid.positions() {
+        if !outside[position] {
+            grid[position] = true
         }
     }
 }
 
-fn largest_rectangle_with_red_tile_corners_and_green_tiles(
+pub fn largest_rectangle_with_red_tile_corners_and_green_tiles(
     red_tile_positions: &[[usize; 2]],
 ) -> usize {
-    let mut rows = HashSet::new();
-    let mut columns = HashSet::new();
-
-    red_tile_positions.iter().for_each(|&[column, row]| {
-        rows.insert(row);
-        columns.insert(column);
-    });
-
-    let mut rows: Vec<usize> = rows.iter().cloned().collect();
-    let max_row = *rows.iter().max().unwrap();
-    let min_row = *rows.iter().min().unwrap();
-    rows.push(max_row + 1);
-    rows.push(min_row - 1);
+    let mut rows: Vec<usize> = red_tile_positions.iter().map(|&[_, row]| row).collect();
     rows.sort();
+    rows.dedup();
 
-    let mut columns: Vec<usize> = columns.iter().cloned().collect();
-    let max_column = *columns.iter().max().unwrap();
-    let min_column = *columns.iter().min().unwrap();
-    columns.push(min_column - 1);
-    columns.push(max_column + 1);
+    let mut columns: Vec<usize> = red_tile_positions
+        .iter()
+        .map(|&[column, _]| column)
+        .collect();
     columns.sort();
+    columns.dedup();
+
+    // each distinct row and column gets a cell of its own, offset by one so there's a border of
+    // cells outside the tiles for the flood fill to start from
+    let compress = |values: &[usize], value: usize| {
+        values
+            .binary_search(&value)
+            .expect("every tile's row and column is listed")
+            + 1
+    };
 
     let compressed_positions: Vec<[usize; 2]> = red_tile_positions
         .iter()
-        .map(|[column, row]| {
-            [
-                columns.iter().position(|c| c == column).unwrap(),
-                rows.iter().position(|r| r == row).unwrap(),
-            ]
-        })
+        .map(|&[column, row]| [compress(&columns, column), compress(&rows, row)])
         .collect();
 
-    let mut grid = vec![vec![false; columns.len()]; rows.len()];
+    let mut grid = Grid::new(columns.len() + 2, rows.len() + 2, false);
 
     for (a, b) in compressed_positions.iter().zip(
         compressed_positions
@@ -156,7 +118,7 @@ fn largest_rectangle_with_red_tile_corners_and_green_tiles(
     ) {
         for row in a[1].min(b[1])..=a[1].max(b[1]) {
             for column in a[0].min(b[0])..=a[0].max(b[0]) {
-                grid[row][column] = true
+                grid[(row, column)] = true
             }
         }
     }
@@ -182,7 +144,7 @@ fn largest_rectangle_with_red_tile_corners_and_green_tiles(
 
         for column in cc[0]..=cc[1] {
             for row in [cr[0], cr[1]] {
-                if !grid[row][column] {
+                if !grid[(row, column)] {
                     valid = false;
                     break;
                 }
@@ -195,7 +157,7 @@ fn largest_rectangle_with_red_tile_corners_and_green_tiles(
 
         for row in cr[0]..=cr[1] {
             for column in [cc[0], cc[1]] {
-                if !grid[row][column] {
+                if !grid[(row, column)] {
                     valid = false;
                     break;
                 }
@@ -213,20 +175,28 @@ fn largest_rectangle_with_red_tile_corners_and_green_tiles(
     0
 }
 
-pub fn solve(input: String) -> anyhow::Result<()> {
-    let red_tile_positions = parse_red_tile_positions(input)?;
+pub struct Day9;
 
-    println!(
-        "part1: {}",
-        largest_rectangle_with_red_tile_corners(&red_tile_positions)
-    );
+impl Solution for Day9 {
+    const DAY: u8 = 9;
 
-    println!(
-        "part2: {}",
-        largest_rectangle_with_red_tile_corners_and_green_tiles(&red_tile_positions,)
-    );
+    type Input<'a> = Vec<[usize; 2]>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    Ok(())
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(parse_red_tile_positions(input)?)
+    }
+
+    fn part1(red_tile_positions: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        Ok(largest_rectangle_with_red_tile_corners(red_tile_positions))
+    }
+
+    fn part2(red_tile_positions: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        Ok(largest_rectangle_with_red_tile_corners_and_green_tiles(
+            red_tile_positions,
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -235,39 +205,59 @@ mod tests {
 
     #[test]
     fn test_largest_rectangle_with_red_tile_corners() {
-        let input = "7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3"
-        .to_string();
+        let input = include_str!("../../../examples/day9/example.txt").to_string();
 
         assert_eq!(
-            largest_rectangle_with_red_tile_corners(&parse_red_tile_positions(input).unwrap()),
+            largest_rectangle_with_red_tile_corners(&parse_red_tile_positions(&input).unwrap()),
             50
         );
     }
 
     #[test]
     fn test_largest_rectangle_with_red_tile_corners_and_green_tiles() {
-        let input = "7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3"
-        .to_string();
-
-        let red_tile_positions = &parse_red_tile_positions(input).unwrap();
+        let input = include_str!("../../../examples/day9/example.txt").to_string();
+
+        let red_tile_positions = &parse_red_tile_positions(&input).unwrap();
 
         assert_eq!(
             largest_rectangle_with_red_tile_corners_and_green_tiles(red_tile_positions,),
             24
         );
     }
+
+    #[test]
+    fn test_tiles_on_the_edges() {
+        let red_tile_positions = parse_red_tile_positions("0,0\n2,0\n2,2\n0,2").unwrap();
+
+        assert_eq!(
+            largest_rectangle_with_red_tile_corners_and_green_tiles(&red_tile_positions),
+            9
+        );
+        assert_eq!(
+            largest_rectangle_with_red_tile_corners_and_green_tiles(&[[0, 0], [2, 2]]),
+            9
+        );
+        assert_eq!(
+            largest_rectangle_with_red_tile_corners_and_green_tiles(&[]),
+            0
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_red_tile_positions("").unwrap_err().to_string(),
+            "line 1, column 1: expected red tile positions, found end of line"
+        );
+        assert_eq!(
+            parse_red_tile_positions("7,1\n11 1")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: expected comma separated column,row, found \"11 1\""
+        );
+        assert_eq!(
+            parse_red_tile_positions("7,-1").unwrap_err().to_string(),
+            "line 1, column 3: expected a row, found \"-1\""
+        );
+    }
 }
@@ -0,0 +1,29 @@
+use crate::{days::day10, solution::Part};
+
+/// Spells out how a day's answer to `part` is reached from its input, a line per step.
+pub fn explain(day: u8, input: &str, part: Part) -> anyhow::Result<Vec<String>> {
+    match day {
+        10 => day10::explain(&day10::parse(input)?, part),
+        _ => anyhow::bail!("day {} has no explanation", day),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(
+            explain(10, "[#] (0) {2}", Part::Two).unwrap(),
+            vec![
+                "line 1: 2 × (0) = 2 presses, giving {2}",
+                "2 presses in total"
+            ]
+        );
+        assert_eq!(
+            explain(4, "", Part::One).unwrap_err().to_string(),
+            "day 4 has no explanation"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{client::Client, input::input_path};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already on disk, so nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure a day's input is in `inputs_dir`, downloading it only if it isn't already there.
+///
+/// The input is written to a temporary file first and moved into place, so an interrupted
+/// download is never mistaken for a cached input.
+pub fn fetch(client: &Client, inputs_dir: &Path, day: u8) -> anyhow::Result<Fetched> {
+    let path = input_path(inputs_dir, day);
+
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = client.input(day)?;
+
+    let dir = path
+        .parent()
+        .expect("input paths are within a day directory");
+    fs::create_dir_all(dir).context(dir.display().to_string())?;
+
+    let partial = path.with_extension("txt.partial");
+    fs::write(&partial, input).context(partial.display().to_string())?;
+    fs::rename(&partial, &path).context(path.display().to_string())?;
+
+    Ok(Fetched::Downloaded(path))
+}
@@ -0,0 +1,137 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{answers::part_answers, days, run::run_day, solution::Part};
+
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// A puzzle example, read from `examples/day<N>/<name>.txt` with its expected answers in
+/// `examples/day<N>/<name>.toml`, e.g.
+///
+/// ```toml
+/// part1 = 3
+/// part2 = 6
+/// ```
+///
+/// Parts without an expected answer are not checked, for examples only given for one part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub day: u8,
+    pub name: String,
+    pub input: PathBuf,
+    pub expected: BTreeMap<Part, String>,
+}
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub part: Part,
+    pub expected: String,
+    pub actual: anyhow::Result<String>,
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        matches!(&self.actual, Ok(actual) if *actual == self.expected)
+    }
+}
+
+/// Finds every fixture under `dir`, ordered by day then name.
+pub fn discover(dir: &Path) -> anyhow::Result<Vec<Fixture>> {
+    let mut fixtures = Vec::new();
+
+    for entry in fs::read_dir(dir).context(dir.display().to_string())? {
+        let path = entry?.path();
+
+        let Some(day) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("day"))
+            .and_then(|day| day.parse::<u8>().ok())
+        else {
+            continue;
+        };
+
+        fixtures.extend(discover_day(&path, day)?);
+    }
+
+    fixtures.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+
+    Ok(fixtures)
+}
+
+/// Finds the fixtures for a single day in `dir`, e.g. `examples/day4`.
+pub fn discover_day(dir: &Path, day: u8) -> anyhow::Result<Vec<Fixture>> {
+    let mut fixtures = Vec::new();
+
+    for entry in fs::read_dir(dir).context(dir.display().to_string())? {
+        let input = entry?.path();
+
+        if input.extension().is_none_or(|extension| extension != "txt") {
+            continue;
+        }
+
+        let name = input
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .ok_or(anyhow::format_err!(
+                "invalid fixture name {}",
+                input.display()
+            ))?
+            .to_string();
+
+        let answers = input.with_extension("toml");
+        let contents = fs::read_to_string(&answers).context(answers.display().to_string())?;
+        let table: toml::Table = contents.parse().context(answers.display().to_string())?;
+
+        fixtures.push(Fixture {
+            day,
+            expected: part_answers(&table, &name)?,
+            name,
+            input,
+        });
+    }
+
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(fixtures)
+}
+
+/// Runs a fixture through its day's solution, comparing each part with an expected answer.
+pub fn check(fixture: &Fixture) -> anyhow::Result<Vec<Outcome>> {
+    let solution = days::get(fixture.day).ok_or(anyhow::format_err!(
+        "no solution registered for day {}",
+        fixture.day
+    ))?;
+
+    let input = fs::read_to_string(&fixture.input).context(fixture.input.display().to_string())?;
+
+    let parts: Vec<Part> = fixture.expected.keys().copied().collect();
+
+    let report = run_day(solution, &input, &parts);
+    report.parse?;
+
+    let mut answers: BTreeMap<Part, anyhow::Result<String>> = report
+        .parts
+        .into_iter()
+        .map(|part| (part.part, part.answer))
+        .collect();
+
+    Ok(fixture
+        .expected
+        .iter()
+        .map(|(&part, expected)| Outcome {
+            part,
+            expected: expected.clone(),
+            actual: answers.remove(&part).unwrap_or(Err(anyhow::format_err!(
+                "day {} has no part {}",
+                fixture.day,
+                part
+            ))),
+        })
+        .collect())
+}
@@ -0,0 +1,356 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::{ParseError, lines};
+
+/// A cell of a grid as `(row, column)`, counted from the top left.
+pub type Position = (usize, usize);
+
+/// `(row, column)` offsets of the 4 cells sharing an edge with a cell.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// `(row, column)` offsets of the 8 cells sharing an edge or a corner with a cell.
+pub const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses one cell per character, with each line of the input a row. Every row must be as
+    /// wide as the first, and `cell` turns a character into a cell or rejects it, in which case
+    /// the error describes the character as `expected`.
+    pub fn parse(
+        input: &str,
+        cell: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for line in lines(input) {
+            let row_width = line.text.chars().count();
+
+            match width {
+                Some(width) if width != row_width => {
+                    return Err(line.error(
+                        line.text,
+                        format!("expected row to be {} wide like the first", width),
+                    ));
+                }
+                _ => width = Some(row_width),
+            }
+
+            for (index, character) in line.text.char_indices() {
+                let part = &line.text[index..index + character.len_utf8()];
+
+                cells.push(
+                    cell(character)
+                        .ok_or_else(|| line.error(part, format!("expected {}", expected)))?,
+                );
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[self.offset(position)])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            let offset = self.offset(position);
+            Some(&mut self.cells[offset])
+        } else {
+            None
+        }
+    }
+
+    fn offset(&self, (row, column): Position) -> usize {
+        row * self.width + column
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "column {} is out of bounds", column);
+
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + use<T> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Positions sharing an edge with `position` that are within the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + use<T> {
+        self.neighbours(position, &ORTHOGONAL)
+    }
+
+    /// Positions sharing an edge or a corner with `position` that are within the grid.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + use<T> {
+        self.neighbours(position, &SURROUNDING)
+    }
+
+    /// Positions `offsets` away from `position` that are within the grid.
+    pub fn neighbours<'a>(
+        &self,
+        (row, column): Position,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + use<'a, T> {
+        let (width, height) = (self.width, self.height);
+
+        offsets
+            .iter()
+            .filter_map(move |&(row_offset, column_offset)| {
+                let row = row
+                    .checked_add_signed(row_offset)
+                    .filter(|&row| row < height)?;
+                let column = column
+                    .checked_add_signed(column_offset)
+                    .filter(|&column| column < width)?;
+
+                Some((row, column))
+            })
+    }
+
+    /// Positions `offsets` away from `position`, wrapping around the edges of the grid as if it
+    /// were a torus.
+    pub fn neighbours_wrapping<'a>(
+        &self,
+        (row, column): Position,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + use<'a, T> {
+        let (width, height) = (self.width as isize, self.height as isize);
+
+        offsets.iter().map(move |&(row_offset, column_offset)| {
+            (
+                (row as isize + row_offset).rem_euclid(height) as usize,
+                (column as isize + column_offset).rem_euclid(width) as usize,
+            )
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws the grid one character per cell, e.g. to print it while debugging.
+    pub fn render(&self, glyph: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&glyph).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        assert!(
+            self.contains(position),
+            "{:?} is out of bounds of a {}x{} grid",
+            position,
+            self.width,
+            self.height
+        );
+
+        &self.cells[self.offset(position)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        assert!(
+            self.contains(position),
+            "{:?} is out of bounds of a {}x{} grid",
+            position,
+            self.width,
+            self.height
+        );
+
+        let offset = self.offset(position);
+        &mut self.cells[offset]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef", Some, "a cell").unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.get((2, 0)), None);
+        assert!(Grid::parse("", Some, "a cell").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Grid::parse("abc\nde", Some, "a cell")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: expected row to be 3 wide like the first, found \"de\""
+        );
+        assert_eq!(
+            Grid::parse(
+                "..#\n.x.",
+                |c| (c == '.' || c == '#').then_some(c),
+                ". or #"
+            )
+            .unwrap_err()
+            .to_string(),
+            "line 2, column 2: expected . or #, found \"x\""
+        );
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = grid();
+
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(
+            grid.iter()
+                .find(|&(_, &c)| c == 'e')
+                .map(|(position, _)| position),
+            Some((1, 1))
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours8((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_neighbours_with_offsets() {
+        let grid = grid();
+
+        assert_eq!(
+            grid.neighbours((0, 1), &[(0, 2), (1, 1), (0, -1)])
+                .collect::<Vec<_>>(),
+            vec![(1, 2), (0, 0)]
+        );
+        assert_eq!(
+            grid.neighbours_wrapping((0, 0), &ORTHOGONAL)
+                .collect::<Vec<_>>(),
+            vec![(1, 0), (0, 2), (0, 1), (1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = grid();
+
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert_eq!(
+            grid.map(|&c| c > 'c')
+                .render(|&c| if c { '#' } else { '.' }),
+            "...\n###"
+        );
+    }
+}
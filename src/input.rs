@@ -0,0 +1,97 @@
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+/// Environment variable overriding the directory puzzle inputs are read from.
+pub const INPUTS_DIR_ENV: &str = "AOC_INPUTS_DIR";
+
+pub const DEFAULT_INPUTS_DIR: &str = "src/days";
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+impl InputSource {
+    /// Parses a `--input` value, where `-` means stdin.
+    pub fn from_arg(value: &str) -> Self {
+        match value {
+            "-" => InputSource::Stdin,
+            _ => InputSource::Path(PathBuf::from(value)),
+        }
+    }
+
+    pub fn read(&self) -> anyhow::Result<String> {
+        match self {
+            InputSource::Path(path) => fs::read_to_string(path).context(path.display().to_string()),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("stdin")?;
+
+                Ok(input)
+            }
+            InputSource::Text(text) => Ok(text.clone()),
+        }
+    }
+}
+
+/// Resolves the inputs directory from the `AOC_INPUTS_DIR` environment variable, falling back
+/// to the inputs checked in alongside each day's module.
+pub fn inputs_dir_from_env() -> PathBuf {
+    std::env::var_os(INPUTS_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or(PathBuf::from(DEFAULT_INPUTS_DIR))
+}
+
+/// Path of a day's input within an inputs directory, e.g. `<inputs_dir>/day4/input.txt`.
+pub fn input_path(inputs_dir: &Path, day: u8) -> PathBuf {
+    inputs_dir.join(format!("day{}", day)).join("input.txt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("inputs/day1.txt"),
+            InputSource::Path(PathBuf::from("inputs/day1.txt"))
+        );
+    }
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(
+            input_path(Path::new("src/days"), 4),
+            PathBuf::from("src/days/day4/input.txt")
+        );
+    }
+
+    #[test]
+    fn test_read_text() {
+        assert_eq!(
+            InputSource::Text("L68\nL30".to_string()).read().unwrap(),
+            "L68\nL30"
+        );
+    }
+
+    #[test]
+    fn test_read_missing_path() {
+        let error = InputSource::Path(PathBuf::from("does/not/exist.txt"))
+            .read()
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "does/not/exist.txt");
+    }
+}
@@ -0,0 +1,278 @@
+use std::ops::RangeInclusive;
+
+/// A set of integers stored as sorted, disjoint and non-adjacent inclusive ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<RangeInclusive<usize>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The set's ranges, in ascending order with gaps between them.
+    pub fn intervals(&self) -> &[RangeInclusive<usize>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of integers in the set, as a `u128` since `0..=usize::MAX` has one more than
+    /// fits in a `usize`.
+    pub fn len(&self) -> u128 {
+        self.intervals
+            .iter()
+            .map(|interval| (interval.end() - interval.start()) as u128 + 1)
+            .sum()
+    }
+
+    /// Whether `value` is in the set, found by binary search over the ranges.
+    pub fn contains(&self, value: usize) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| *interval.end() < value);
+
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| *interval.start() <= value)
+    }
+
+    /// Adds `range`, merging it with the ranges it overlaps or touches, which are found by
+    /// binary search.
+    pub fn insert(&mut self, range: RangeInclusive<usize>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let (mut start, mut end) = range.into_inner();
+
+        // the ranges from `first` up to `last` overlap or touch the new one
+        let first = self.intervals.partition_point(|interval| {
+            interval
+                .end()
+                .checked_add(1)
+                .is_some_and(|next| next < start)
+        });
+        let last = self.intervals.partition_point(|interval| {
+            end.checked_add(1)
+                .is_none_or(|next| *interval.start() <= next)
+        });
+
+        if first < last {
+            start = start.min(*self.intervals[first].start());
+            end = end.max(*self.intervals[last - 1].end());
+        }
+
+        self.intervals.splice(first..last, [start..=end]);
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .cloned()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            let start = *a.start().max(b.start());
+            let end = *a.end().min(b.end());
+
+            if start <= end {
+                intervals.push(start..=end);
+            }
+
+            // whichever ends first can't overlap anything further along the other
+            if a.end() < b.end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        // the overlaps of disjoint, sorted ranges are themselves disjoint and sorted
+        IntervalSet { intervals }
+    }
+
+    /// The integers in `self` that aren't in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        match (self.intervals.first(), self.intervals.last()) {
+            (Some(first), Some(last)) => {
+                self.intersection(&other.complement(*first.start()..=*last.end()))
+            }
+            _ => IntervalSet::new(),
+        }
+    }
+
+    /// The integers within `bounds` that aren't in the set.
+    pub fn complement(&self, bounds: RangeInclusive<usize>) -> IntervalSet {
+        let mut intervals = Vec::new();
+
+        if bounds.is_empty() {
+            return IntervalSet { intervals };
+        }
+
+        let (start, end) = bounds.into_inner();
+        // the next integer that could be in the complement, or None once past usize::MAX
+        let mut next = Some(start);
+
+        for interval in &self.intervals {
+            let Some(from) = next else {
+                break;
+            };
+
+            if *interval.end() < from {
+                continue;
+            }
+
+            if *interval.start() > end {
+                break;
+            }
+
+            if *interval.start() > from {
+                intervals.push(from..=interval.start() - 1);
+            }
+
+            next = interval.end().checked_add(1);
+        }
+
+        if let Some(from) = next
+            && from <= end
+        {
+            intervals.push(from..=end);
+        }
+
+        IntervalSet { intervals }
+    }
+}
+
+impl FromIterator<RangeInclusive<usize>> for IntervalSet {
+    /// Sorts the ranges and merges any that overlap or touch, dropping empty ones.
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<usize>>>(ranges: I) -> Self {
+        let mut ranges: Vec<RangeInclusive<usize>> = ranges
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .collect();
+
+        ranges.sort_by_key(|range| *range.start());
+
+        let mut intervals: Vec<RangeInclusive<usize>> = Vec::with_capacity(ranges.len());
+
+        for range in ranges {
+            match intervals.last_mut() {
+                Some(last)
+                    if last
+                        .end()
+                        .checked_add(1)
+                        .is_none_or(|next| *range.start() <= next) =>
+                {
+                    *last = *last.start()..=*last.end().max(range.end());
+                }
+                _ => intervals.push(range),
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<usize>]) -> IntervalSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_normalizes() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let set = set(&[10..=14, 3..=5, 16..=20, 12..=18, 6..=6, 30..=29]);
+
+        assert_eq!(set.intervals(), &[3..=6, 10..=20]);
+        assert_eq!(set.len(), 15);
+    }
+
+    #[test]
+    fn test_contains() {
+        let set = set(&[3..=5, 10..=14]);
+
+        assert_eq!(
+            (0..16)
+                .filter(|&value| set.contains(value))
+                .collect::<Vec<_>>(),
+            vec![3, 4, 5, 10, 11, 12, 13, 14]
+        );
+        assert!(!IntervalSet::new().contains(0));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[1..=5, 10..=20]);
+        let b = set(&[4..=12, 18..=25]);
+
+        assert_eq!(a.union(&b).intervals(), &[1..=25]);
+        assert_eq!(a.intersection(&b).intervals(), &[4..=5, 10..=12, 18..=20]);
+        assert_eq!(a.difference(&b).intervals(), &[1..=3, 13..=17]);
+        assert_eq!(b.difference(&a).intervals(), &[6..=9, 21..=25]);
+        assert_eq!(a.complement(0..=22).intervals(), &[0..=0, 6..=9, 21..=22]);
+        assert_eq!(a.complement(3..=12).intervals(), &[6..=9]);
+
+        let mut c = a.clone();
+        c.insert(6..=9);
+        assert_eq!(c.intervals(), &[1..=20]);
+    }
+
+    #[test]
+    fn test_bounds_of_usize() {
+        let set = set(&[usize::MAX - 1..=usize::MAX, 0..=0]);
+
+        assert_eq!(
+            set.complement(0..=usize::MAX).intervals(),
+            &[1..=usize::MAX - 2]
+        );
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(usize::MAX));
+
+        let everything = set.union(&set.complement(0..=usize::MAX));
+        assert_eq!(everything.intervals(), &[0..=usize::MAX]);
+        assert_eq!(everything.len(), usize::MAX as u128 + 1);
+    }
+
+    #[test]
+    fn test_insert_matches_collecting() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let ranges = [
+            10..=14,
+            3..=5,
+            16..=20,
+            6..=6,
+            30..=29,
+            22..=22,
+            0..=1,
+            12..=21,
+            usize::MAX..=usize::MAX,
+            24..=usize::MAX - 1,
+        ];
+
+        let mut inserted = IntervalSet::new();
+
+        for (count, range) in ranges.iter().enumerate() {
+            inserted.insert(range.clone());
+
+            assert_eq!(inserted, set(&ranges[..=count]), "after {:?}", range);
+        }
+
+        assert_eq!(
+            inserted.intervals(),
+            &[0..=1, 3..=6, 10..=22, 24..=usize::MAX]
+        );
+    }
+}
@@ -0,0 +1,23 @@
+pub mod answers;
+pub mod bench;
+pub mod bitgrid;
+pub mod cli;
+pub mod client;
+pub mod days;
+pub mod explain;
+pub mod fetch;
+pub mod fixtures;
+pub mod grid;
+pub mod input;
+pub mod interval_set;
+pub mod output;
+pub mod parse;
+pub mod pool;
+pub mod run;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+#[cfg(test)]
+mod testing;
+pub mod visualize;
+pub mod watch;
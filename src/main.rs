@@ -1,52 +1,535 @@
-#![feature(result_option_map_or_default)]
-use std::fs;
+use std::{path::PathBuf, time::Duration};
 
-mod days;
+use anyhow::Context as _;
 
-use anyhow::Context;
+use advent_of_code_2025::{
+    answers::DEFAULT_ANSWERS_PATH,
+    cli::{self, Context, run::Visualize},
+    client::Client,
+    days::{self, day4},
+    input::{InputSource, inputs_dir_from_env},
+    output::Format,
+    pool,
+    solution::Part,
+    submit::DEFAULT_GUESSES_PATH,
+    visualize::FrameFormat,
+};
 
-use crate::days::*;
+const USAGE: &str = "usage: advent-of-code-2025 [run] <days> [--jobs <n>] [options]
+       advent-of-code-2025 [run] <day> --visualize [--frame-delay <ms>] [--frames <dir>] [options]
+       advent-of-code-2025 [run] <day> --explain [options]
+       advent-of-code-2025 [run] 4 --rule <rule> [--visualize] [options]
+       advent-of-code-2025 bench <days> [--iterations <n>] [--warmup <n>] [options]
+       advent-of-code-2025 verify [<days>] [--answers <path>] [options]
+       advent-of-code-2025 fetch <days> [--session <token>] [--base-url <url>] [--inputs-dir <dir>]
+       advent-of-code-2025 submit <day> --part 1|2 [--answer <answer>] [--guesses <path>] [options]
+       advent-of-code-2025 watch <day> [--interval <ms>] [options]
+       advent-of-code-2025 new <day>
+
+<days> is a day (3), a range (1-5), a comma separated list (3,7,11) or all
+
+options:
+--part 1|2|both selects which parts to run, defaulting to both
+--format text|json prints answers as text, or as a JSON record per part and line
+
+--input reads a single day's input from a file, or from stdin when given -
+--input-text takes a single day's input inline
+--inputs-dir reads <dir>/day<N>/input.txt for each day, defaulting to $AOC_INPUTS_DIR or src/days
+
+run options:
+--jobs is the number of days to run at once, defaulting to the number of CPUs
+several days are run in parallel and summarised in a table
+--visualize animates a day in the terminal, e.g. each round of day 4's removals
+--frame-delay is how long each frame is shown in milliseconds, defaulting to 200
+--frames also writes each frame to a numbered file in <dir>
+--frame-format text|ppm writes frames as plain text or PPM images, defaulting to text
+--explain shows how a day's answers are reached, e.g. which buttons to press on day 10
+--rule changes which rolls of paper day 4 counts as accessible, when running or visualizing it,
+as comma separated changes to the puzzle's rule: rolls=<chars>, threshold=<n>, wrap, and
+neighbourhood=moore|von-neumann|<row>:<column>;..., e.g. --rule neighbourhood=von-neumann,wrap
+
+bench options:
+--iterations is the number of timed runs of each phase, defaulting to 10
+--warmup is the number of untimed runs before timing, defaulting to 3
+
+verify options:
+--answers reads known answers from a TOML file, defaulting to answers.toml
+verify checks every registered day when <days> is omitted
+
+fetch options:
+--session is the session cookie of a logged in account, defaulting to $AOC_SESSION
+--base-url is the server to download from, defaulting to $AOC_BASE_URL or https://adventofcode.com
+inputs already in the inputs directory are never downloaded again
+
+submit options:
+--answer is the answer to submit, defaulting to the one computed from the day's input
+--guesses records every submitted answer, defaulting to guesses.jsonl
+--session and --base-url are as for fetch
+answers already known to be wrong, outside the too high and too low bounds of earlier answers,
+or sent before a wait the server asked for has passed are refused without being submitted
+
+watch options:
+--interval is how often to check for changes in milliseconds, defaulting to 500
+watch re-runs a day and its examples whenever its input or example files change, showing how
+each answer compares with the previous run
+
+new creates src/days/day<N>/mod.rs and examples/day<N>/ for a day, and registers it in src/days.rs";
+
+enum Command {
+    Run {
+        jobs: usize,
+        visualize: Option<Visualize>,
+        explain: bool,
+        rule: Option<day4::Rule>,
+    },
+    Bench {
+        iterations: usize,
+        warmup: usize,
+    },
+    Verify {
+        answers: PathBuf,
+    },
+    Fetch {
+        base_url: Option<String>,
+        session: Option<String>,
+    },
+    Submit {
+        client: Client,
+        answer: Option<String>,
+        guesses: PathBuf,
+    },
+    Watch {
+        interval: Duration,
+    },
+    New,
+}
 
 struct Args {
-    day: u8,
+    command: Command,
+    context: Context,
+}
+
+fn option_value(
+    args: &mut impl Iterator<Item = String>,
+    arg: &str,
+    option: &str,
+) -> anyhow::Result<Option<String>> {
+    if arg == option {
+        return args.next().map(Some).ok_or(anyhow::format_err!(
+            "expected value for {}\n\n{}",
+            option,
+            USAGE
+        ));
+    }
+
+    Ok(arg
+        .strip_prefix(option)
+        .and_then(|rest| rest.strip_prefix('='))
+        .map(str::to_string))
+}
+
+/// Parses a day selection such as `4`, `1-5`, `3,7,11`, `1-3,7` or `all`.
+fn parse_days(value: &str) -> anyhow::Result<Vec<u8>> {
+    if value == "all" {
+        return Ok(days::SOLUTIONS
+            .iter()
+            .map(|solution| solution.day())
+            .collect());
+    }
+
+    let mut selected = Vec::new();
+
+    for item in value.split(',') {
+        let (start, end) = match item.split_once('-') {
+            Some((start, end)) => (start.parse::<u8>()?, end.parse::<u8>()?),
+            None => {
+                let day = item.parse::<u8>()?;
+                (day, day)
+            }
+        };
+
+        if start > end {
+            anyhow::bail!("expected day range start to be before end, got {}", item);
+        }
+
+        for day in start..=end {
+            if days::get(day).is_none() {
+                anyhow::bail!("no solution registered for day {}", day);
+            }
+
+            if !selected.contains(&day) {
+                selected.push(day);
+            }
+        }
+    }
+
+    selected.sort();
+
+    Ok(selected)
+}
+
+fn parse_parts(value: &str) -> anyhow::Result<Vec<Part>> {
+    match value {
+        "both" => Ok(vec![Part::One, Part::Two]),
+        _ => Ok(vec![value.parse()?]),
+    }
 }
 
 impl Args {
     pub fn parse() -> anyhow::Result<Self> {
-        let args = std::env::args().collect::<Vec<String>>();
+        let mut args = std::env::args().skip(1);
+
+        let mut command = None;
+        let mut days = None;
+        let mut iterations = None;
+        let mut warmup = None;
+        let mut answers = None;
+        let mut jobs = None;
+        let mut session = None;
+        let mut base_url = None;
+        let mut answer = None;
+        let mut guesses = None;
+        let mut interval = None;
+        let mut visualize = false;
+        let mut explain = false;
+        let mut frame_delay = None;
+        let mut frames = None;
+        let mut frame_format = None;
+        let mut rule = None;
+        let mut format = Format::Text;
+        let mut parts = None;
+        let mut input = None;
+        let mut inputs_dir = None;
+
+        while let Some(arg) = args.next() {
+            if let Some(value) = option_value(&mut args, &arg, "--part")? {
+                parts = Some(parse_parts(&value)?);
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--input")? {
+                input = Some(InputSource::from_arg(&value));
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--input-text")? {
+                input = Some(InputSource::Text(value));
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--inputs-dir")? {
+                inputs_dir = Some(PathBuf::from(value));
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--iterations")? {
+                iterations = Some(value.parse().context("invalid --iterations")?);
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--warmup")? {
+                warmup = Some(value.parse().context("invalid --warmup")?);
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--jobs")? {
+                jobs = Some(value.parse().context("invalid --jobs")?);
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--format")? {
+                format = value.parse()?;
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--answers")? {
+                answers = Some(PathBuf::from(value));
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--session")? {
+                session = Some(value);
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--base-url")? {
+                base_url = Some(value);
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--answer")? {
+                answer = Some(value);
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--guesses")? {
+                guesses = Some(PathBuf::from(value));
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--interval")? {
+                interval = Some(value.parse().context("invalid --interval")?);
+                continue;
+            }
+
+            if let Some(value) = option_value(&mut args, &arg, "--frame-delay")? {
+                frame_delay = Some(value.parse().context("invalid --frame-delay")?);
+                continue;
+            }
 
-        match args.len() {
-            2 => {
-                let day: u8 = args[1].parse()?;
+            if let Some(value) = option_value(&mut args, &arg, "--frames")? {
+                frames = Some(PathBuf::from(value));
+                continue;
+            }
 
-                Ok(Self { day })
+            if let Some(value) = option_value(&mut args, &arg, "--frame-format")? {
+                frame_format = Some(value.parse()?);
+                continue;
             }
-            1 => anyhow::bail!("not enough arguments; want day"),
-            _ => anyhow::bail!("too many arguments; want day"),
+
+            if let Some(value) = option_value(&mut args, &arg, "--rule")? {
+                rule = Some(value.parse::<day4::Rule>().context("invalid --rule")?);
+                continue;
+            }
+
+            match arg.as_str() {
+                "--visualize" => visualize = true,
+                "--explain" => explain = true,
+                "run" | "bench" | "verify" | "fetch" | "submit" | "watch" | "new"
+                    if command.is_none() && days.is_none() =>
+                {
+                    command = Some(arg)
+                }
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                _ if arg.starts_with('-') => anyhow::bail!("unknown option {}\n\n{}", arg, USAGE),
+                // a new day isn't registered yet, so doesn't go through parse_days
+                _ if command.as_deref() == Some("new") && days.is_none() => {
+                    days = Some(vec![arg.parse().context(format!("invalid day {}", arg))?]);
+                }
+                _ if days.is_none() => {
+                    days = Some(parse_days(&arg).context(format!("invalid days {}", arg))?);
+                }
+                _ => anyhow::bail!("too many arguments; want days\n\n{}", USAGE),
+            }
+        }
+
+        if command.as_deref() != Some("bench") && (iterations.is_some() || warmup.is_some()) {
+            anyhow::bail!("--iterations and --warmup can only be used with bench");
         }
+
+        if !matches!(command.as_deref(), Some("fetch" | "submit"))
+            && (session.is_some() || base_url.is_some())
+        {
+            anyhow::bail!("--session and --base-url can only be used with fetch and submit");
+        }
+
+        if command.as_deref() == Some("fetch") && (input.is_some() || parts.is_some()) {
+            anyhow::bail!("--input, --input-text and --part can't be used with fetch");
+        }
+
+        if command.as_deref() != Some("submit") && (answer.is_some() || guesses.is_some()) {
+            anyhow::bail!("--answer and --guesses can only be used with submit");
+        }
+
+        if command.as_deref() == Some("submit")
+            && parts.as_ref().is_none_or(|parts| parts.len() != 1)
+        {
+            anyhow::bail!("submit needs a single --part of 1 or 2");
+        }
+
+        if matches!(
+            command.as_deref(),
+            Some("bench" | "verify" | "fetch" | "submit" | "watch" | "new")
+        ) && format != Format::Text
+        {
+            anyhow::bail!("--format can only be used with run");
+        }
+
+        if matches!(
+            command.as_deref(),
+            Some("bench" | "verify" | "fetch" | "submit" | "watch" | "new")
+        ) && jobs.is_some()
+        {
+            anyhow::bail!("--jobs can only be used with run");
+        }
+
+        if jobs == Some(0) {
+            anyhow::bail!("expected --jobs to be at least 1");
+        }
+
+        if command.as_deref() != Some("watch") && interval.is_some() {
+            anyhow::bail!("--interval can only be used with watch");
+        }
+
+        if command.as_deref() == Some("watch") && input == Some(InputSource::Stdin) {
+            anyhow::bail!("watch can't read input from stdin");
+        }
+
+        if command.as_deref().is_some_and(|command| command != "run") && visualize {
+            anyhow::bail!("--visualize can only be used with run");
+        }
+
+        if !visualize && (frame_delay.is_some() || frames.is_some() || frame_format.is_some()) {
+            anyhow::bail!(
+                "--frame-delay, --frames and --frame-format can only be used with --visualize"
+            );
+        }
+
+        if visualize && format != Format::Text {
+            anyhow::bail!("--visualize can't be used with --format");
+        }
+
+        if command.as_deref().is_some_and(|command| command != "run") && explain {
+            anyhow::bail!("--explain can only be used with run");
+        }
+
+        if explain && (visualize || format != Format::Text) {
+            anyhow::bail!("--explain can't be used with --visualize or --format");
+        }
+
+        if command.as_deref().is_some_and(|command| command != "run") && rule.is_some() {
+            anyhow::bail!("--rule can only be used with run");
+        }
+
+        if rule.is_some() && (explain || format != Format::Text) {
+            anyhow::bail!("--rule can't be used with --explain or --format");
+        }
+
+        if command.as_deref() != Some("verify") && answers.is_some() {
+            anyhow::bail!("--answers can only be used with verify");
+        }
+
+        let command = match command.as_deref() {
+            Some("bench") => Command::Bench {
+                iterations: iterations.unwrap_or(10),
+                warmup: warmup.unwrap_or(3),
+            },
+            Some("verify") => {
+                days.get_or_insert_with(|| parse_days("all").expect("all is a valid selection"));
+
+                Command::Verify {
+                    answers: answers.unwrap_or(PathBuf::from(DEFAULT_ANSWERS_PATH)),
+                }
+            }
+            Some("fetch") => Command::Fetch { base_url, session },
+            Some("submit") => Command::Submit {
+                client: Client::from_env(base_url, session)?,
+                answer,
+                guesses: guesses.unwrap_or(PathBuf::from(DEFAULT_GUESSES_PATH)),
+            },
+            Some("watch") => Command::Watch {
+                interval: Duration::from_millis(interval.unwrap_or(500)),
+            },
+            Some("new") => Command::New,
+            _ => Command::Run {
+                jobs: jobs.unwrap_or_else(pool::default_threads),
+                visualize: visualize.then(|| Visualize {
+                    delay: Duration::from_millis(frame_delay.unwrap_or(200)),
+                    frames,
+                    format: frame_format.unwrap_or(FrameFormat::Text),
+                }),
+                explain,
+                rule,
+            },
+        };
+
+        let days = days.ok_or(anyhow::format_err!(
+            "not enough arguments; want days\n\n{}",
+            USAGE
+        ))?;
+
+        if input.is_some() && days.len() > 1 {
+            anyhow::bail!("--input and --input-text can only be used with a single day");
+        }
+
+        if matches!(command, Command::Submit { .. } | Command::Watch { .. }) && days.len() != 1 {
+            anyhow::bail!("submit and watch need a single day");
+        }
+
+        if (visualize || explain) && days.len() != 1 {
+            anyhow::bail!("--visualize and --explain need a single day");
+        }
+
+        if matches!(command, Command::Run { rule: Some(_), .. }) && days != [4] {
+            anyhow::bail!("--rule only applies to day 4");
+        }
+
+        Ok(Self {
+            command,
+            context: Context {
+                days,
+                parts: parts.unwrap_or(vec![Part::One, Part::Two]),
+                input,
+                inputs_dir: inputs_dir.unwrap_or_else(inputs_dir_from_env),
+                format,
+            },
+        })
     }
 }
 
 fn main() -> anyhow::Result<()> {
-    let args = Args::parse()?;
-    let path = format!("src/days/day{}/input.txt", args.day);
-    let input = fs::read_to_string(&path).context(path)?;
-
-    match args.day {
-        1 => day1::solve(input)?,
-        2 => day2::solve(input)?,
-        3 => day3::solve(input)?,
-        4 => day4::solve(input)?,
-        5 => day5::solve(input)?,
-        6 => day6::solve(input)?,
-        7 => day7::solve(input)?,
-        8 => day8::solve(input)?,
-        9 => day9::solve(input)?,
-        10 => day10::solve(input)?,
-        11 => day11::solve(input)?,
-        12 => day12::solve(input)?,
-        _ => anyhow::bail!("expected day between 1 and 12, got {}", args.day),
-    }
-
-    Ok(())
+    let Args { command, context } = Args::parse()?;
+
+    match command {
+        Command::Run {
+            visualize: Some(ref visualize),
+            ref rule,
+            ..
+        } => cli::run::visualize(&context, visualize, rule.as_ref()),
+        Command::Run { explain: true, .. } => cli::run::explain(&context),
+        Command::Run {
+            rule: Some(ref rule),
+            ..
+        } => cli::run::with_rule(&context, rule),
+        Command::Run { jobs, .. } => cli::run::run(&context, jobs),
+        Command::Bench { iterations, warmup } => cli::bench::run(&context, iterations, warmup),
+        Command::Verify { ref answers } => cli::verify::run(&context, answers),
+        Command::Fetch { base_url, session } => cli::fetch::run(&context, base_url, session),
+        Command::Submit {
+            ref client,
+            ref answer,
+            ref guesses,
+        } => cli::submit::run(&context, client, answer.as_deref(), guesses),
+        Command::Watch { interval } => cli::watch::run(&context, interval),
+        Command::New => cli::new::run(context.days[0]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("4").unwrap(), vec![4]);
+        assert_eq!(parse_days("1-5").unwrap(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(parse_days("11,3,7").unwrap(), vec![3, 7, 11]);
+        assert_eq!(parse_days("1-3,2,7").unwrap(), vec![1, 2, 3, 7]);
+        assert_eq!(
+            parse_days("all").unwrap(),
+            days::SOLUTIONS
+                .iter()
+                .map(|solution| solution.day())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_days_invalid() {
+        assert!(parse_days("5-1").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("x").is_err());
+        assert!(parse_days("").is_err());
+    }
+
+    #[test]
+    fn test_parse_parts() {
+        assert_eq!(parse_parts("1").unwrap(), vec![Part::One]);
+        assert_eq!(parse_parts("2").unwrap(), vec![Part::Two]);
+        assert_eq!(parse_parts("both").unwrap(), vec![Part::One, Part::Two]);
+        assert!(parse_parts("3").is_err());
+    }
 }
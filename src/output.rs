@@ -0,0 +1,145 @@
+use serde::Serialize;
+
+use crate::{run::DayReport, solution::Part};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => anyhow::bail!("expected format of text or json, got {}", value),
+        }
+    }
+}
+
+/// One part's result, as emitted one per line by `--format json`.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration_ns: Option<u128>,
+    pub error: Option<String>,
+}
+
+impl Record {
+    fn error(day: u8, part: Part, error: &anyhow::Error) -> Self {
+        Self {
+            day,
+            part: part.number(),
+            answer: None,
+            duration_ns: None,
+            error: Some(format!("{:#}", error)),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records only hold strings and integers")
+    }
+}
+
+/// Flattens a day's report into a record per part.
+///
+/// When the day failed to parse, each of `parts` gets a record carrying the parse error.
+pub fn records(report: &DayReport, parts: &[Part]) -> Vec<Record> {
+    let day = report.day;
+
+    if let Err(error) = &report.parse {
+        return parts
+            .iter()
+            .map(|&part| Record::error(day, part, error))
+            .collect();
+    }
+
+    report
+        .parts
+        .iter()
+        .map(|part| match &part.answer {
+            Ok(answer) => Record {
+                day,
+                part: part.part.number(),
+                answer: Some(answer.clone()),
+                duration_ns: Some(part.duration.as_nanos()),
+                error: None,
+            },
+            Err(error) => Record {
+                duration_ns: Some(part.duration.as_nanos()),
+                ..Record::error(day, part.part, error)
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::run::PartReport;
+
+    #[test]
+    fn test_records() {
+        let report = DayReport {
+            day: 10,
+            parse: Ok(Duration::from_micros(5)),
+            parts: vec![
+                PartReport {
+                    part: Part::One,
+                    answer: Ok("7".to_string()),
+                    duration: Duration::from_nanos(1500),
+                },
+                PartReport {
+                    part: Part::Two,
+                    answer: Err(anyhow::format_err!("not yet implemented")),
+                    duration: Duration::from_nanos(20),
+                },
+            ],
+        };
+
+        assert_eq!(
+            records(&report, &[Part::One, Part::Two])
+                .iter()
+                .map(Record::to_json)
+                .collect::<Vec<_>>(),
+            vec![
+                r#"{"day":10,"part":1,"answer":"7","duration_ns":1500,"error":null}"#,
+                r#"{"day":10,"part":2,"answer":null,"duration_ns":20,"error":"not yet implemented"}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_records_parse_error() {
+        let report = DayReport {
+            day: 1,
+            parse: Err(anyhow::format_err!("expected direction of L or R")),
+            parts: Vec::new(),
+        };
+
+        assert_eq!(
+            records(&report, &[Part::Two]),
+            vec![Record {
+                day: 1,
+                part: 2,
+                answer: None,
+                duration_ns: None,
+                error: Some("expected direction of L or R".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("text".parse::<Format>().unwrap(), Format::Text);
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
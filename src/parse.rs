@@ -0,0 +1,200 @@
+use std::{fmt::Display, str::FromStr};
+
+/// An error in a puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-indexed line of the input.
+    pub line: usize,
+    /// 1-indexed column, counted in characters.
+    pub column: usize,
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        snippet: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            line,
+            column,
+            snippet: snippet.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Places an error found when parsing a lone line (e.g. through `FromStr`) on `line` of a
+    /// larger input.
+    pub fn on_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+
+        if self.snippet.is_empty() {
+            write!(f, ", found end of line")
+        } else {
+            write!(f, ", found {:?}", self.snippet)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A line of input along with its 1-indexed line number.
+///
+/// Slices of `text` (from `split`, `trim` and the like) keep track of where they came from, so
+/// errors about them can point at the right column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Self {
+        Self { number, text }
+    }
+
+    /// 1-indexed column that `part` starts at, or just past the end of the line if `part` isn't
+    /// a slice of it.
+    pub fn column(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).wrapping_sub(start);
+
+        if offset <= self.text.len() && self.text.is_char_boundary(offset) {
+            self.text[..offset].chars().count() + 1
+        } else {
+            self.text.chars().count() + 1
+        }
+    }
+
+    pub fn error(&self, part: &str, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, self.column(part), part, message)
+    }
+
+    /// Error for something missing at the end of the line.
+    pub fn error_at_end(&self, message: impl Into<String>) -> ParseError {
+        self.error(&self.text[self.text.len()..], message)
+    }
+
+    /// Parses `part`, describing it as `expected` (e.g. "a number") if it fails.
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.parse()
+            .map_err(|_| self.error(part, format!("expected {}", expected)))
+    }
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Line::new(index + 1, text))
+}
+
+/// Splits the input into blocks of lines separated by blank lines.
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![Vec::new()];
+
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            if !sections.last().is_some_and(Vec::is_empty) {
+                sections.push(Vec::new());
+            }
+        } else {
+            sections
+                .last_mut()
+                .expect("at least one section")
+                .push(line);
+        }
+    }
+
+    if sections.last().is_some_and(Vec::is_empty) {
+        sections.pop();
+    }
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column() {
+        let line = Line::new(3, "ab,cd,ef");
+        let parts: Vec<&str> = line.text.split(',').collect();
+
+        assert_eq!(line.column(parts[0]), 1);
+        assert_eq!(line.column(parts[2]), 7);
+        assert_eq!(line.column(&line.text[line.text.len()..]), 9);
+        assert_eq!(line.column("unrelated"), 9);
+    }
+
+    #[test]
+    fn test_column_counts_characters() {
+        let line = Line::new(1, "é,x");
+
+        assert_eq!(line.column(line.text.split(',').nth(1).unwrap()), 3);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let line = Line::new(2, "12,x4");
+        let (_, right) = line.text.split_once(',').unwrap();
+
+        let error = line.parse::<usize>(right, "a number").unwrap_err();
+
+        assert_eq!(error, ParseError::new(2, 4, "x4", "expected a number"));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: expected a number, found \"x4\""
+        );
+    }
+
+    #[test]
+    fn test_error_at_end() {
+        assert_eq!(
+            Line::new(1, "abc").error_at_end("expected ','").to_string(),
+            "line 1, column 4: expected ',', found end of line"
+        );
+    }
+
+    #[test]
+    fn test_on_line() {
+        assert_eq!(
+            ParseError::new(1, 2, "x", "oops").on_line(7),
+            ParseError::new(7, 2, "x", "oops")
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(
+            lines("a\nb").collect::<Vec<_>>(),
+            vec![Line::new(1, "a"), Line::new(2, "b")]
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        assert_eq!(
+            sections("a\nb\n\nc\n\n\nd\n"),
+            vec![
+                vec![Line::new(1, "a"), Line::new(2, "b")],
+                vec![Line::new(4, "c")],
+                vec![Line::new(7, "d")],
+            ]
+        );
+    }
+}
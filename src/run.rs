@@ -0,0 +1,220 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    panic::{self, AssertUnwindSafe, catch_unwind, resume_unwind},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::solution::{Part, Runner};
+
+/// Answer to a single part, along with how long it took to compute.
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: anyhow::Result<String>,
+    pub duration: Duration,
+}
+
+/// Outcome of running a day: the parse phase, then each selected part.
+///
+/// When parsing fails `parse` holds the error and no parts are run.
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: u8,
+    pub parse: anyhow::Result<Duration>,
+    pub parts: Vec<PartReport>,
+}
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+
+    (value, start.elapsed())
+}
+
+thread_local! {
+    /// Whether this thread is inside `catch_panic`, so a panic will be reported as an error.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, turning a panic into an error so a buggy solution can't take down its caller.
+pub fn catch_panic<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    let catching = CATCHING.replace(true);
+    let result = catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+
+    result.unwrap_or_else(|panic| Err(anyhow::format_err!("panicked: {}", panic_message(&*panic))))
+}
+
+/// Runs `f` without printing the panics `catch_panic` turns into errors, which are reported
+/// with the rest of the results instead. Other panics are printed as usual.
+pub fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    let hook: Arc<dyn Fn(&panic::PanicHookInfo<'_>) + Send + Sync> = Arc::from(panic::take_hook());
+
+    panic::set_hook(Box::new({
+        let hook = hook.clone();
+
+        move |info| {
+            if !CATCHING.get() {
+                hook(info)
+            }
+        }
+    }));
+
+    let result = catch_unwind(AssertUnwindSafe(f));
+
+    panic::set_hook(Box::new(move |info| hook(info)));
+
+    result.unwrap_or_else(|panic| resume_unwind(panic))
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Parses `input` and answers each of `parts` the day has a puzzle for, timing every phase.
+pub fn run_day(solution: &dyn Runner, input: &str, parts: &[Part]) -> DayReport {
+    let day = solution.day();
+
+    let (parsed, parse_duration) = time(|| catch_panic(|| solution.parse(input)));
+
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            return DayReport {
+                day,
+                parse: Err(error),
+                parts: Vec::new(),
+            };
+        }
+    };
+
+    let parts = solution
+        .parts()
+        .iter()
+        .filter(|part| parts.contains(part))
+        .map(|&part| {
+            let (answer, duration) = time(|| catch_panic(|| parsed.solve(part)));
+
+            PartReport {
+                part,
+                answer,
+                duration,
+            }
+        })
+        .collect();
+
+    DayReport {
+        day,
+        parse: Ok(parse_duration),
+        parts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, solution::Solution};
+
+    #[test]
+    fn test_run_day() {
+        let report = run_day(days::get(3).unwrap(), "9876", &[Part::One, Part::Two]);
+
+        assert_eq!(report.day, 3);
+        assert!(report.parse.is_ok());
+        assert_eq!(
+            report
+                .parts
+                .iter()
+                .map(|part| (part.part, part.answer.as_ref().unwrap().as_str()))
+                .collect::<Vec<_>>(),
+            vec![(Part::One, "98"), (Part::Two, "0")]
+        );
+    }
+
+    #[test]
+    fn test_run_day_only_selected_parts() {
+        let report = run_day(days::get(12).unwrap(), "0:\n#\n\n1x1: 1", &[Part::Two]);
+
+        assert!(report.parse.is_ok());
+        assert!(report.parts.is_empty());
+    }
+
+    #[test]
+    fn test_run_day_parse_error() {
+        let report = run_day(days::get(1).unwrap(), "X1", &[Part::One]);
+
+        assert!(report.parse.is_err());
+        assert!(report.parts.is_empty());
+    }
+
+    struct Panics;
+
+    impl Solution for Panics {
+        const DAY: u8 = 99;
+
+        type Input<'a> = &'a str;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+            Ok(input)
+        }
+
+        fn part1(input: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+            Ok(input.len())
+        }
+
+        fn part2(input: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+            Ok(input.as_bytes()[input.len()] as usize)
+        }
+    }
+
+    #[test]
+    fn test_run_day_isolates_panics() {
+        let report = run_day(&Panics, "abc", &[Part::One, Part::Two]);
+
+        assert_eq!(report.parts[0].answer.as_ref().unwrap(), "3");
+        assert!(
+            report.parts[1]
+                .answer
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .starts_with("panicked: index out of bounds")
+        );
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| Ok(1)).unwrap(), 1);
+        assert_eq!(
+            catch_panic(|| -> anyhow::Result<()> { panic!("index {} out of bounds", 3) })
+                .unwrap_err()
+                .to_string(),
+            "panicked: index 3 out of bounds"
+        );
+        assert_eq!(
+            catch_panic(|| -> anyhow::Result<()> { panic!("oops") })
+                .unwrap_err()
+                .to_string(),
+            "panicked: oops"
+        );
+    }
+
+    #[test]
+    fn test_quietly() {
+        let report = quietly(|| run_day(&Panics, "abc", &[Part::Two]));
+
+        assert!(report.parts[0].answer.is_err());
+        assert_eq!(quietly(|| catch_panic(|| Ok(1)).unwrap()), 1);
+        assert!(catch_unwind(|| quietly(|| panic!("not caught"))).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::fixtures::EXAMPLES_DIR;
+
+/// The module registering every day, relative to the crate root.
+pub const DAYS_MODULE: &str = "src/days.rs";
+
+pub const DAYS_DIR: &str = "src/days";
+
+/// Skeleton of a day's module, with `{day}` standing in for the day number.
+const MODULE_TEMPLATE: &str = r#"use crate::{
+    parse::{ParseError, lines},
+    solution::Solution,
+};
+
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    Ok(lines(input).map(|line| line.text).collect())
+}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u8 = {day};
+
+    type Input<'a> = Vec<&'a str>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(_: &Self::Input<'_>) -> anyhow::Result<Self::Part1> {
+        anyhow::bail!("part 1 is not yet implemented")
+    }
+
+    fn part2(_: &Self::Input<'_>) -> anyhow::Result<Self::Part2> {
+        anyhow::bail!("part 2 is not yet implemented")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "not yet implemented"]
+    fn test_part1() {
+        let input = include_str!("../../../examples/day{day}/example.txt");
+
+        assert_eq!(Day{day}::part1(&parse(input).unwrap()).unwrap(), 0);
+    }
+
+    #[test]
+    #[ignore = "not yet implemented"]
+    fn test_part2() {
+        let input = include_str!("../../../examples/day{day}/example.txt");
+
+        assert_eq!(Day{day}::part2(&parse(input).unwrap()).unwrap(), 0);
+    }
+}
+"#;
+
+const EXAMPLE_ANSWERS_TEMPLATE: &str =
+    "# expected answers for example.txt, checked once uncommented
+# part1 = 0
+# part2 = 0
+";
+
+pub fn module_source(day: u8) -> String {
+    MODULE_TEMPLATE.replace("{day}", &day.to_string())
+}
+
+/// Adds `day` to the source of `days.rs`: a `mod` declaration kept in the sorted order rustfmt
+/// expects, and an entry in `SOLUTIONS` kept in day order.
+pub fn register(days_module: &str, day: u8) -> anyhow::Result<String> {
+    let module = format!("day{}", day);
+    let declaration = format!("pub mod {};", module);
+    let entry = format!("    &{}::Day{},", module, day);
+
+    let mut lines: Vec<&str> = days_module.lines().collect();
+
+    if lines.contains(&declaration.as_str()) {
+        anyhow::bail!("day {} is already registered", day);
+    }
+
+    let declared = |line: &str| {
+        line.strip_prefix("pub mod ")
+            .and_then(|line| line.strip_suffix(';'))
+            .map(str::to_string)
+    };
+
+    let last_declaration = lines
+        .iter()
+        .rposition(|line| declared(line).is_some())
+        .ok_or(anyhow::format_err!("expected pub mod declarations"))?;
+
+    let position = lines
+        .iter()
+        .position(|line| declared(line).is_some_and(|declared| declared > module))
+        .unwrap_or(last_declaration + 1);
+
+    lines.insert(position, &declaration);
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static SOLUTIONS"))
+        .ok_or(anyhow::format_err!("expected a SOLUTIONS registry"))?;
+
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| *line == "];")
+            .ok_or(anyhow::format_err!("expected SOLUTIONS to end with ];"))?;
+
+    let registered = |line: &str| -> Option<u8> {
+        line.trim()
+            .strip_prefix("&day")?
+            .split_once("::")?
+            .0
+            .parse()
+            .ok()
+    };
+
+    let position = (start + 1..end)
+        .find(|&index| registered(lines[index]).is_some_and(|registered| registered > day))
+        .unwrap_or(end);
+
+    lines.insert(position, &entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates the module and example fixture for `day` under the crate at `root`, and registers it
+/// in `days.rs`. Returns the files written.
+pub fn scaffold(root: &Path, day: u8) -> anyhow::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        anyhow::bail!("expected a day from 1 to 25, got {}", day);
+    }
+
+    let module_dir = root.join(DAYS_DIR).join(format!("day{}", day));
+    let module = module_dir.join("mod.rs");
+    let examples = root.join(EXAMPLES_DIR).join(format!("day{}", day));
+    let example = examples.join("example.txt");
+    let answers = examples.join("example.toml");
+    let days_module = root.join(DAYS_MODULE);
+
+    for path in [&module, &example, &answers] {
+        if path.exists() {
+            anyhow::bail!("{} already exists", path.display());
+        }
+    }
+
+    let registered = register(
+        &fs::read_to_string(&days_module).context(days_module.display().to_string())?,
+        day,
+    )?;
+
+    for dir in [&module_dir, &examples] {
+        fs::create_dir_all(dir).context(dir.display().to_string())?;
+    }
+
+    fs::write(&module, module_source(day)).context(module.display().to_string())?;
+    fs::write(&example, "").context(example.display().to_string())?;
+    fs::write(&answers, EXAMPLE_ANSWERS_TEMPLATE).context(answers.display().to_string())?;
+    fs::write(&days_module, registered).context(days_module.display().to_string())?;
+
+    Ok(vec![module, example, answers, days_module])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    const DAYS_MODULE_SOURCE: &str = "use crate::solution::Runner;
+
+pub mod day1;
+pub mod day12;
+pub mod day2;
+
+pub static SOLUTIONS: &[&dyn Runner] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day12::Day12,
+];
+";
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register(DAYS_MODULE_SOURCE, 3).unwrap(),
+            "use crate::solution::Runner;
+
+pub mod day1;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+
+pub static SOLUTIONS: &[&dyn Runner] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day12::Day12,
+];
+"
+        );
+    }
+
+    #[test]
+    fn test_register_keeps_order() {
+        let registered = register(DAYS_MODULE_SOURCE, 10).unwrap();
+
+        assert!(registered.contains("pub mod day1;\npub mod day10;\npub mod day12;"));
+        assert!(registered.contains("&day2::Day2,\n    &day10::Day10,\n    &day12::Day12,"));
+
+        let registered = register(DAYS_MODULE_SOURCE, 25).unwrap();
+
+        assert!(registered.contains("pub mod day2;\npub mod day25;\n"));
+        assert!(registered.contains("&day12::Day12,\n    &day25::Day25,\n];"));
+    }
+
+    #[test]
+    fn test_register_existing_day() {
+        assert_eq!(
+            register(DAYS_MODULE_SOURCE, 12).unwrap_err().to_string(),
+            "day 12 is already registered"
+        );
+    }
+
+    #[test]
+    fn test_register_matches_days_module() {
+        let days_module = include_str!("days.rs");
+
+        for solution in days::SOLUTIONS {
+            assert!(register(days_module, solution.day()).is_err());
+        }
+
+        let last = days::SOLUTIONS.last().unwrap().day();
+
+        assert!(register(days_module, last + 1).unwrap().contains(&format!(
+            "&day{}::Day{},\n    &day{}::Day{},\n];",
+            last,
+            last,
+            last + 1,
+            last + 1
+        )));
+    }
+
+    #[test]
+    fn test_module_source() {
+        let source = module_source(13);
+
+        assert!(source.contains("pub struct Day13;"));
+        assert!(source.contains("const DAY: u8 = 13;"));
+        assert!(source.contains("examples/day13/example.txt"));
+        assert!(!source.contains("{day}"));
+    }
+}
@@ -0,0 +1,91 @@
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => anyhow::bail!("expected part of 1 or 2, got {}", value),
+        }
+    }
+}
+
+/// A day's puzzle, split into parsing the input and answering each part from the parsed input.
+pub trait Solution {
+    const DAY: u8;
+
+    /// Parts with a puzzle to answer; the final day only has one.
+    const PARTS: &'static [Part] = &[Part::One, Part::Two];
+
+    type Input<'a>;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>>;
+
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Self::Part1>;
+
+    fn part2(input: &Self::Input<'_>) -> anyhow::Result<Self::Part2>;
+}
+
+/// Object-safe view of a [`Solution`], so every day can sit in the same registry.
+pub trait Runner: Sync {
+    fn day(&self) -> u8;
+
+    fn parts(&self) -> &'static [Part];
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Box<dyn Parsed + 'a>>;
+}
+
+/// A day's parsed input, ready to answer either part.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> anyhow::Result<String>;
+}
+
+struct ParsedInput<'a, S: Solution>(S::Input<'a>);
+
+impl<S: Solution> Parsed for ParsedInput<'_, S> {
+    fn solve(&self, part: Part) -> anyhow::Result<String> {
+        match part {
+            Part::One => S::part1(&self.0).map(|answer| answer.to_string()),
+            Part::Two => S::part2(&self.0).map(|answer| answer.to_string()),
+        }
+    }
+}
+
+impl<S: Solution + Sync + 'static> Runner for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parts(&self) -> &'static [Part] {
+        S::PARTS
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Box<dyn Parsed + 'a>> {
+        Ok(Box::new(ParsedInput::<S>(S::parse(input)?)))
+    }
+}
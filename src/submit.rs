@@ -0,0 +1,493 @@
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{client::Client, solution::Part};
+
+pub const DEFAULT_GUESSES_PATH: &str = "guesses.jsonl";
+
+/// How the server judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    /// Wrong, without a hint of which way.
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// Not judged, as an answer was submitted too recently.
+    Wait,
+}
+
+impl Verdict {
+    fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wait => write!(f, "not judged, submitted too recently"),
+        }
+    }
+}
+
+/// The server's response to a submission: a verdict, and how long to wait before submitting
+/// again if it said.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    pub wait: Option<Duration>,
+}
+
+/// Reads the verdict out of the page the server responds to a submission with.
+pub fn parse_response(page: &str) -> anyhow::Result<Response> {
+    let text = article_text(page);
+
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::Wait
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        anyhow::bail!("the server says this part is already solved or not yet unlocked")
+    } else {
+        anyhow::bail!("unrecognised response to submission: {}", text);
+    };
+
+    Ok(Response {
+        verdict,
+        wait: parse_wait(&text),
+    })
+}
+
+/// Text of the page's `<article>`, where the server explains its verdict, without tags.
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses waits given as "You have 1m 30s left to wait" or "please wait 5 minutes before
+/// trying again".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((_, rest)) = text.split_once("You have ") {
+        let (left, _) = rest.split_once(" left to wait")?;
+
+        return left
+            .split_whitespace()
+            .map(|amount| {
+                let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+                let number: u64 = number.parse().ok()?;
+
+                match unit {
+                    "h" => Some(number * 60 * 60),
+                    "m" => Some(number * 60),
+                    "s" => Some(number),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    let (_, rest) = text.split_once("wait ")?;
+    let (amount, _) = rest.split_once(" before trying again")?;
+
+    let minutes = match amount {
+        "one minute" => 1,
+        _ => amount.strip_suffix(" minutes")?.parse().ok()?,
+    };
+
+    Some(Duration::from_secs(minutes * 60))
+}
+
+/// A submitted answer, as recorded one per line in the guesses file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub submitted_at: u64,
+    /// Seconds the server asked to wait before submitting again.
+    pub wait: Option<u64>,
+}
+
+impl Guess {
+    fn is_for(&self, day: u8, part: Part) -> bool {
+        self.day == day && self.part == part.number()
+    }
+}
+
+/// Every answer submitted so far, used to avoid submitting answers already known to be wrong.
+#[derive(Debug, Default)]
+pub struct Guesses {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+pub fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+impl Guesses {
+    /// Loads the guesses recorded at `path`, or none if it doesn't exist yet.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let guesses = match fs::read_to_string(path) {
+            Ok(contents) => contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .enumerate()
+                .map(|(index, line)| {
+                    serde_json::from_str(line).context(format!(
+                        "{}: line {}",
+                        path.display(),
+                        index + 1
+                    ))
+                })
+                .collect::<anyhow::Result<_>>()?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error).context(path.display().to_string()),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            guesses,
+        })
+    }
+
+    pub fn for_part(&self, day: u8, part: Part) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |guess| guess.is_for(day, part))
+    }
+
+    /// Explains why `answer` shouldn't be submitted at `now`, if there's a reason not to.
+    pub fn check(&self, day: u8, part: Part, answer: &str, now: u64) -> anyhow::Result<()> {
+        let guesses: Vec<&Guess> = self.for_part(day, part).collect();
+
+        if let Some(correct) = guesses
+            .iter()
+            .find(|guess| guess.verdict == Verdict::Correct)
+        {
+            anyhow::bail!(
+                "day {} part {} is already solved with {}",
+                day,
+                part,
+                correct.answer
+            );
+        }
+
+        if let Some(guess) = guesses
+            .iter()
+            .find(|guess| guess.verdict.is_wrong() && guess.answer == answer)
+        {
+            anyhow::bail!("{} was already submitted and was {}", answer, guess.verdict);
+        }
+
+        if let Ok(number) = answer.parse::<i128>() {
+            let numbers = |verdict| {
+                guesses
+                    .iter()
+                    .filter(move |guess| guess.verdict == verdict)
+                    .filter_map(|guess| guess.answer.parse::<i128>().ok())
+            };
+
+            if let Some(high) = numbers(Verdict::TooHigh).min()
+                && number >= high
+            {
+                anyhow::bail!("{} is not below {}, which was too high", answer, high);
+            }
+
+            if let Some(low) = numbers(Verdict::TooLow).max()
+                && number <= low
+            {
+                anyhow::bail!("{} is not above {}, which was too low", answer, low);
+            }
+        }
+
+        // the wait applies to every submission from the account, whatever the day
+        if let Some(until) = self
+            .guesses
+            .iter()
+            .filter_map(|guess| Some(guess.submitted_at + guess.wait?))
+            .max()
+            && now < until
+        {
+            anyhow::bail!(
+                "the server asked to wait another {}s before submitting again",
+                until - now
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Appends a guess to the file the guesses were loaded from.
+    pub fn record(&mut self, guess: Guess) -> anyhow::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context(self.path.display().to_string())?;
+
+        writeln!(
+            file,
+            "{}",
+            serde_json::to_string(&guess).expect("guesses only hold strings and integers")
+        )
+        .context(self.path.display().to_string())?;
+
+        self.guesses.push(guess);
+
+        Ok(())
+    }
+}
+
+/// Submits `answer` unless an earlier guess rules it out, recording the server's verdict.
+pub fn submit(
+    client: &Client,
+    guesses: &mut Guesses,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> anyhow::Result<Response> {
+    let answer = answer.trim();
+
+    if answer.is_empty() {
+        anyhow::bail!("expected a non-empty answer");
+    }
+
+    let now = unix_time(SystemTime::now());
+
+    guesses
+        .check(day, part, answer, now)
+        .context(format!("refusing to submit {}", answer))?;
+
+    let response = parse_response(&client.answer(day, part, answer)?)?;
+
+    guesses.record(Guess {
+        day,
+        part: part.number(),
+        answer: answer.to_string(),
+        verdict: response.verdict,
+        submitted_at: now,
+        wait: response.wait.map(|wait| wait.as_secs()),
+    })?;
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            message
+        )
+    }
+
+    fn guess(answer: &str, verdict: Verdict) -> Guess {
+        Guess {
+            day: 1,
+            part: 1,
+            answer: answer.to_string(),
+            verdict,
+            submitted_at: 1000,
+            wait: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer."
+            ))
+            .unwrap(),
+            Response {
+                verdict: Verdict::Correct,
+                wait: None
+            }
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again."
+            ))
+            .unwrap(),
+            Response {
+                verdict: Verdict::TooHigh,
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low. Please wait 5 minutes before trying again."
+            ))
+            .unwrap(),
+            Response {
+                verdict: Verdict::TooLow,
+                wait: Some(Duration::from_secs(300))
+            }
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer.")).unwrap(),
+            Response {
+                verdict: Verdict::Incorrect,
+                wait: None
+            }
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 30s left to wait."
+            ))
+            .unwrap(),
+            Response {
+                verdict: Verdict::Wait,
+                wait: Some(Duration::from_secs(90))
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_response_unrecognised() {
+        assert!(
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+            .is_err()
+        );
+        assert_eq!(
+            parse_response(&page("<b>Teapot</b>"))
+                .unwrap_err()
+                .to_string(),
+            "unrecognised response to submission: Teapot"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let guesses = Guesses {
+            path: PathBuf::new(),
+            guesses: vec![
+                guess("100", Verdict::TooHigh),
+                guess("20", Verdict::TooLow),
+                guess("50", Verdict::Incorrect),
+                guess("90", Verdict::TooHigh),
+            ],
+        };
+
+        assert!(guesses.check(1, Part::One, "60", 1000).is_ok());
+        assert!(guesses.check(1, Part::Two, "100", 1000).is_ok());
+        assert!(guesses.check(2, Part::One, "100", 1000).is_ok());
+
+        assert_eq!(
+            guesses
+                .check(1, Part::One, "50", 1000)
+                .unwrap_err()
+                .to_string(),
+            "50 was already submitted and was wrong"
+        );
+        assert_eq!(
+            guesses
+                .check(1, Part::One, "95", 1000)
+                .unwrap_err()
+                .to_string(),
+            "95 is not below 90, which was too high"
+        );
+        assert_eq!(
+            guesses
+                .check(1, Part::One, "3", 1000)
+                .unwrap_err()
+                .to_string(),
+            "3 is not above 20, which was too low"
+        );
+    }
+
+    #[test]
+    fn test_check_solved() {
+        let guesses = Guesses {
+            path: PathBuf::new(),
+            guesses: vec![guess("42", Verdict::Correct)],
+        };
+
+        assert_eq!(
+            guesses
+                .check(1, Part::One, "43", 1000)
+                .unwrap_err()
+                .to_string(),
+            "day 1 part 1 is already solved with 42"
+        );
+    }
+
+    #[test]
+    fn test_check_wait() {
+        let guesses = Guesses {
+            path: PathBuf::new(),
+            guesses: vec![Guess {
+                wait: Some(60),
+                ..guess("7", Verdict::Wait)
+            }],
+        };
+
+        assert_eq!(
+            guesses
+                .check(1, Part::Two, "8", 1030)
+                .unwrap_err()
+                .to_string(),
+            "the server asked to wait another 30s before submitting again"
+        );
+        assert_eq!(
+            guesses
+                .check(2, Part::One, "8", 1030)
+                .unwrap_err()
+                .to_string(),
+            "the server asked to wait another 30s before submitting again"
+        );
+        assert!(guesses.check(1, Part::Two, "8", 1060).is_ok());
+        assert!(guesses.check(2, Part::One, "8", 1060).is_ok());
+    }
+}
@@ -0,0 +1,22 @@
+//! Helpers shared by the unit tests.
+
+mod temp_dir;
+
+pub use temp_dir::TempDir;
+
+/// A xorshift64 pseudo-random number generator, giving the same numbers for the same seed.
+pub struct Random(u64);
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero
+        Self(seed.max(1))
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -0,0 +1,327 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    thread::sleep,
+    time::Duration,
+};
+
+use anyhow::Context;
+
+use crate::{days::day4, grid::Grid};
+
+/// A snapshot of a day's grid: each cell's character, or `None` once it's cleared away, and
+/// whether the cell changes in this frame. Changes are kept in a layer of their own rather than
+/// drawn as a character, which could be one the day's cells use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub cells: Grid<Option<char>>,
+    pub changed: Grid<bool>,
+}
+
+impl Display for Frame {
+    /// The frame's characters, with changed cells in red and cleared ones as spaces.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (row, cells) in self.cells.rows().enumerate() {
+            if row > 0 {
+                writeln!(f)?;
+            }
+
+            for (column, cell) in cells.iter().enumerate() {
+                match (cell, self.changed[(row, column)]) {
+                    (Some(cell), true) => write!(f, "\x1b[31m{}\x1b[0m", cell)?,
+                    (Some(cell), false) => write!(f, "{}", cell)?,
+                    (None, _) => write!(f, " ")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A day's frames, and the colour of each character in images of them.
+pub struct Visualization {
+    pub frames: Vec<Frame>,
+    pub colour: Box<dyn Fn(char) -> [u8; 3]>,
+}
+
+/// How frames are written to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFormat {
+    /// The frame's characters, one line per row, with changed cells in red by terminal escape
+    /// codes.
+    Text,
+    /// A binary PPM image with a pixel per cell.
+    Ppm,
+}
+
+impl FrameFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            FrameFormat::Text => "txt",
+            FrameFormat::Ppm => "ppm",
+        }
+    }
+}
+
+impl std::str::FromStr for FrameFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(FrameFormat::Text),
+            "ppm" => Ok(FrameFormat::Ppm),
+            _ => anyhow::bail!("expected frame format of text or ppm, got {}", value),
+        }
+    }
+}
+
+/// The frames of a day that can be visualized, from its input. Day 4 removes rolls by `rule`,
+/// defaulting to the puzzle's.
+pub fn frames(day: u8, input: &str, rule: Option<&day4::Rule>) -> anyhow::Result<Visualization> {
+    match day {
+        4 => {
+            let grid = day4::parse(input)?;
+            let rule = rule.cloned().unwrap_or_default();
+
+            Ok(Visualization {
+                frames: removal_frames(&grid, &day4::removal_rounds(&grid, &rule)),
+                colour: Box::new(move |cell| if rule.is_roll(cell) { GREY } else { WHITE }),
+            })
+        }
+        _ => anyhow::bail!("day {} has no visualization", day),
+    }
+}
+
+/// The grid before any rolls are removed, followed by a frame per round with the rolls removed in
+/// that round changed and those removed in earlier rounds cleared.
+fn removal_frames(grid: &Grid<char>, rounds: &Grid<Option<usize>>) -> Vec<Frame> {
+    let last = rounds
+        .iter()
+        .filter_map(|(_, &round)| round)
+        .max()
+        .unwrap_or(0);
+
+    (0..=last)
+        .map(|round| {
+            let mut cells = grid.map(|&cell| Some(cell));
+
+            for (position, removed) in rounds.iter() {
+                if removed.is_some_and(|removed| removed < round) {
+                    cells[position] = None;
+                }
+            }
+
+            Frame {
+                cells,
+                changed: rounds.map(|&removed| removed == Some(round)),
+            }
+        })
+        .collect()
+}
+
+/// Draws each frame in place in the terminal, waiting `delay` after each.
+pub fn animate(out: &mut impl Write, frames: &[Frame], delay: Duration) -> anyhow::Result<()> {
+    for (index, frame) in frames.iter().enumerate() {
+        // clear the screen and move the cursor to the top left
+        write!(out, "\x1b[2J\x1b[H")?;
+        writeln!(out, "frame {} of {}", index, frames.len() - 1)?;
+        writeln!(out, "{}", frame)?;
+        out.flush()?;
+
+        sleep(delay);
+    }
+
+    Ok(())
+}
+
+const WHITE: [u8; 3] = [255, 255, 255];
+const GREY: [u8; 3] = [70, 70, 70];
+const RED: [u8; 3] = [220, 40, 40];
+
+/// Encodes a frame as a binary PPM image, with changed cells in red, cleared ones in white and
+/// the rest coloured by `colour`.
+pub fn ppm(frame: &Frame, colour: impl Fn(char) -> [u8; 3]) -> Vec<u8> {
+    let mut image = format!(
+        "P6\n{} {}\n255\n",
+        frame.cells.width(),
+        frame.cells.height()
+    )
+    .into_bytes();
+
+    for (position, &cell) in frame.cells.iter() {
+        image.extend(match cell {
+            _ if frame.changed[position] => RED,
+            Some(cell) => colour(cell),
+            None => WHITE,
+        });
+    }
+
+    image
+}
+
+/// Writes each frame to a numbered file in `dir`, e.g. `frame-003.ppm`, returning the paths
+/// written.
+pub fn write_frames(
+    dir: &Path,
+    visualization: &Visualization,
+    format: FrameFormat,
+) -> anyhow::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir).context(dir.display().to_string())?;
+
+    visualization
+        .frames
+        .iter()
+        .enumerate()
+        .map(|(index, frame)| {
+            let path = dir.join(format!("frame-{:03}.{}", index, format.extension()));
+
+            let contents = match format {
+                FrameFormat::Text => format!("{}\n", frame).into_bytes(),
+                FrameFormat::Ppm => ppm(frame, &visualization.colour),
+            };
+
+            fs::write(&path, contents).context(path.display().to_string())?;
+
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    fn frame() -> Frame {
+        Frame {
+            cells: Grid::parse("@x\n..", Some, "a cell")
+                .unwrap()
+                .map(|&cell| Some(cell)),
+            changed: Grid::parse("..\n..", |cell| Some(cell == 'x'), "a cell").unwrap(),
+        }
+    }
+
+    fn visualization() -> Visualization {
+        Visualization {
+            frames: vec![frame(), frame()],
+            colour: Box::new(|_| GREY),
+        }
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut frame = frame();
+        frame.cells[(1, 1)] = None;
+        frame.changed[(0, 1)] = true;
+
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([70, 70, 70, 220, 40, 40]);
+        expected.extend([70, 70, 70, 255, 255, 255]);
+
+        assert_eq!(ppm(&frame, |_| GREY), expected);
+    }
+
+    #[test]
+    fn test_animate() {
+        let mut out = Vec::new();
+
+        animate(&mut out, &[frame(), frame()], Duration::ZERO).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("\x1b[2J").count(), 2);
+        assert!(out.ends_with("frame 1 of 1\n@x\n..\n"));
+    }
+
+    #[test]
+    fn test_write_frames() {
+        let dir = TempDir::new("frames");
+
+        let paths = write_frames(dir.path(), &visualization(), FrameFormat::Text).unwrap();
+
+        assert_eq!(
+            paths,
+            vec![dir.join("frame-000.txt"), dir.join("frame-001.txt")]
+        );
+        assert_eq!(fs::read_to_string(&paths[1]).unwrap(), "@x\n..\n");
+    }
+
+    #[test]
+    fn test_frames() {
+        assert_eq!(frames(4, "@@\n@@", None).unwrap().frames.len(), 2);
+        // each roll has 3 neighbours, so none are ever accessible with a threshold of 3
+        let rule = "threshold=3".parse().unwrap();
+        assert_eq!(frames(4, "@@\n@@", Some(&rule)).unwrap().frames.len(), 1);
+        assert_eq!(
+            frames(3, "", None).err().unwrap().to_string(),
+            "day 3 has no visualization"
+        );
+    }
+
+    #[test]
+    fn test_removal_frames() {
+        let input = include_str!("../examples/day4/example.txt");
+
+        let frames = frames(4, input, None).unwrap().frames;
+
+        assert_eq!(frames.len(), 10);
+        assert_eq!(frames[0].to_string(), input.trim_end());
+        assert_eq!(
+            frames[1]
+                .changed
+                .render(|&changed| if changed { '*' } else { ' ' }),
+            "  ** ** * 
+*         
+      *   
+          
+*        *
+          
+          
+*         
+          
+* *     * "
+        );
+        assert_eq!(
+            frames[2].cells.render(|cell| cell.unwrap_or(' ')),
+            "..  .  @ .
+ @@.@.@.@@
+@@@@@. .@@
+@.@@@@..@.
+ @.@@@@.@ 
+.@@@@@@@.@
+.@.@.@.@@@
+ .@@@.@@@@
+.@@@@@@@@.
+ . .@@@. ."
+        );
+        assert_eq!(
+            frames[9]
+                .changed
+                .iter()
+                .filter(|&(_, &changed)| changed)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_colour_by_rule() {
+        let rule = "rolls=x#".parse().unwrap();
+        let visualization = frames(4, "x#\n@.", Some(&rule)).unwrap();
+
+        // x is a roll here, and changes without being mistaken for the marker of a change
+        assert_eq!((visualization.colour)('x'), GREY);
+        assert_eq!((visualization.colour)('#'), GREY);
+        assert_eq!((visualization.colour)('@'), WHITE);
+        assert_eq!(
+            ppm(&visualization.frames[0], &visualization.colour)[11..],
+            [GREY, GREY, WHITE, WHITE].concat()
+        );
+        assert_eq!(
+            visualization.frames[1].to_string(),
+            "\x1b[31mx\x1b[0m\x1b[31m#\x1b[0m\n@."
+        );
+    }
+}
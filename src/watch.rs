@@ -0,0 +1,285 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    thread::sleep,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    fixtures::{check, discover_day},
+    run::run_day,
+    solution::{Part, Runner},
+};
+
+/// Last modified time of each watched file, or `None` for files that don't exist (yet).
+pub type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+/// The files a day's results depend on: its input, if read from a file, and every file in its
+/// examples directory.
+pub fn watched_files(input: Option<&Path>, examples: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = input.into_iter().map(Path::to_path_buf).collect();
+
+    if let Ok(entries) = fs::read_dir(examples) {
+        let mut examples: Vec<PathBuf> = entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.is_file())
+            .collect();
+
+        examples.sort();
+        paths.extend(examples);
+    }
+
+    paths
+}
+
+/// Calls `run` straight away, then again whenever a file listed by `files` changes, checking
+/// every `interval`. The list is taken afresh each time, so new example files are picked up.
+pub fn watch(files: impl Fn() -> Vec<PathBuf>, interval: Duration, mut run: impl FnMut()) -> ! {
+    let mut last = None;
+
+    loop {
+        let current = snapshot(&files());
+
+        if last.as_ref() != Some(&current) {
+            run();
+            last = Some(current);
+        }
+
+        sleep(interval);
+    }
+}
+
+/// Results of one run of a day, keyed by a label such as `input part1` or `example part2`.
+pub type Results = BTreeMap<String, String>;
+
+/// Answers `parts` of the day's input, then checks each of its examples against their expected
+/// answers.
+pub fn results(
+    solution: &dyn Runner,
+    input: anyhow::Result<String>,
+    parts: &[Part],
+    examples: &Path,
+) -> Results {
+    let mut results = Results::new();
+
+    match input {
+        Ok(input) => {
+            let report = run_day(solution, &input, parts);
+
+            match report.parse {
+                Ok(_) => {
+                    for part in report.parts {
+                        let result = part
+                            .answer
+                            .unwrap_or_else(|error| format!("error: {:#}", error));
+
+                        results.insert(format!("input part{}", part.part), result);
+                    }
+                }
+                Err(error) => {
+                    results.insert("input".to_string(), format!("error: {:#}", error));
+                }
+            }
+        }
+        Err(error) => {
+            results.insert("input".to_string(), format!("error: {:#}", error));
+        }
+    }
+
+    let fixtures = match discover_day(examples, solution.day()) {
+        Ok(fixtures) => fixtures,
+        // a day doesn't need examples
+        Err(_) if !examples.exists() => Vec::new(),
+        Err(error) => {
+            results.insert("examples".to_string(), format!("error: {:#}", error));
+            Vec::new()
+        }
+    };
+
+    for fixture in fixtures {
+        match check(&fixture) {
+            Ok(outcomes) => {
+                for outcome in outcomes {
+                    let result = match &outcome.actual {
+                        _ if outcome.passed() => "pass".to_string(),
+                        Ok(actual) => format!("FAIL expected {}, got {}", outcome.expected, actual),
+                        Err(error) => {
+                            format!("FAIL expected {}, got error: {:#}", outcome.expected, error)
+                        }
+                    };
+
+                    results.insert(format!("{} part{}", fixture.name, outcome.part), result);
+                }
+            }
+            Err(error) => {
+                results.insert(fixture.name, format!("error: {:#}", error));
+            }
+        }
+    }
+
+    results
+}
+
+/// How one result compares with the previous run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Unchanged(String),
+    Changed { previous: String, current: String },
+    Added(String),
+    Removed(String),
+}
+
+/// Compares each result with the same label in the previous run.
+pub fn diff(previous: &Results, current: &Results) -> Vec<(String, Change)> {
+    let mut labels: Vec<&String> = previous.keys().chain(current.keys()).collect();
+    labels.sort();
+    labels.dedup();
+
+    labels
+        .into_iter()
+        .map(|label| {
+            let change = match (previous.get(label), current.get(label)) {
+                (Some(previous), Some(current)) if previous == current => {
+                    Change::Unchanged(current.clone())
+                }
+                (Some(previous), Some(current)) => Change::Changed {
+                    previous: previous.clone(),
+                    current: current.clone(),
+                },
+                (None, Some(current)) => Change::Added(current.clone()),
+                (Some(previous), None) => Change::Removed(previous.clone()),
+                (None, None) => unreachable!("labels come from one of the runs"),
+            };
+
+            (label.clone(), change)
+        })
+        .collect()
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Unchanged(result) => write!(f, "{}", result),
+            Change::Changed { previous, current } => write!(f, "{} (was {})", current, previous),
+            Change::Added(result) => write!(f, "{} (new)", result),
+            Change::Removed(result) => write!(f, "gone (was {})", result),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use crate::testing::TempDir;
+
+    fn results(results: &[(&str, &str)]) -> Results {
+        results
+            .iter()
+            .map(|(label, result)| (label.to_string(), result.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff() {
+        let previous = results(&[
+            ("example part1", "pass"),
+            ("input part1", "12"),
+            ("input part2", "error: oops"),
+        ]);
+        let current = results(&[
+            ("example part1", "pass"),
+            ("input part1", "13"),
+            ("input", "error: line 1"),
+        ]);
+
+        assert_eq!(
+            diff(&previous, &current),
+            vec![
+                (
+                    "example part1".to_string(),
+                    Change::Unchanged("pass".to_string())
+                ),
+                (
+                    "input".to_string(),
+                    Change::Added("error: line 1".to_string())
+                ),
+                (
+                    "input part1".to_string(),
+                    Change::Changed {
+                        previous: "12".to_string(),
+                        current: "13".to_string()
+                    }
+                ),
+                (
+                    "input part2".to_string(),
+                    Change::Removed("error: oops".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_notices_changes() {
+        let dir = TempDir::new("watch");
+        let path = dir.join("input.txt");
+
+        let paths = vec![path.clone()];
+        let missing = snapshot(&paths);
+        assert_eq!(missing[&path], None);
+
+        fs::write(&path, "1").unwrap();
+        let created = snapshot(&paths);
+        assert_ne!(created, missing);
+
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        assert_ne!(snapshot(&paths), created);
+    }
+
+    #[test]
+    fn test_results() {
+        let examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/day3");
+
+        assert_eq!(
+            super::results(
+                days::get(3).unwrap(),
+                Ok("9876".to_string()),
+                &[Part::One, Part::Two],
+                &examples
+            ),
+            results(&[
+                ("example part1", "pass"),
+                ("example part2", "pass"),
+                ("input part1", "98"),
+                ("input part2", "0"),
+            ])
+        );
+
+        assert_eq!(
+            super::results(
+                days::get(3).unwrap(),
+                Err(anyhow::format_err!("missing")),
+                &[Part::One],
+                Path::new("does/not/exist")
+            ),
+            results(&[("input", "error: missing")])
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use advent_of_code_2025::{
+    days::{self, day1, day3, day10, day12},
+    solution::{Part, Solution},
+};
+
+#[test]
+fn test_dial() {
+    let mut dial = day1::Dial::new();
+
+    dial.rotate(&day1::Rotation::new(day1::Direction::Left, 68));
+
+    assert_eq!(dial.clicks(), 82);
+}
+
+#[test]
+fn test_bank_max_joltage() {
+    let bank: day3::Bank = "818181911112111".parse().unwrap();
+
+    assert_eq!(bank.max_joltage(2), 92);
+}
+
+#[test]
+fn test_machine() {
+    let machine: day10::Machine = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}"
+        .parse()
+        .unwrap();
+
+    assert_eq!(machine.joltage_requirements(), &[3, 5, 4, 7]);
+    assert_eq!(
+        machine.min_button_presses_to_configure_indicator_lights(),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_region_can_fit() {
+    let region = day12::Region::new(4, 4, vec![2]);
+
+    assert!(region.can_fit(&[vec![vec![true; 3], vec![true, false, false], vec![true; 3]]]));
+}
+
+#[test]
+fn test_solution_through_trait_and_registry() {
+    let input = "987654321111111\n811111111111119";
+
+    let banks = day3::Day3::parse(input).unwrap();
+    assert_eq!(day3::Day3::part1(&banks).unwrap(), 98 + 89);
+
+    let parsed = days::get(3).unwrap().parse(input).unwrap();
+    assert_eq!(parsed.solve(Part::One).unwrap(), "187");
+}
@@ -0,0 +1,30 @@
+use std::path::Path;
+
+use advent_of_code_2025::fixtures::{EXAMPLES_DIR, check, discover};
+
+#[test]
+fn test_examples() {
+    let fixtures = discover(&Path::new(env!("CARGO_MANIFEST_DIR")).join(EXAMPLES_DIR)).unwrap();
+
+    assert!(!fixtures.is_empty(), "expected examples to be discovered");
+
+    let mut failures = Vec::new();
+
+    for fixture in &fixtures {
+        let label = format!("day{}/{}", fixture.day, fixture.name);
+
+        match check(fixture) {
+            Ok(outcomes) => {
+                for outcome in outcomes.iter().filter(|outcome| !outcome.passed()) {
+                    failures.push(format!(
+                        "{} part {}: expected {}, got {:?}",
+                        label, outcome.part, outcome.expected, outcome.actual
+                    ));
+                }
+            }
+            Err(error) => failures.push(format!("{}: {:#}", label, error)),
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use std::fs;
+
+use advent_of_code_2025::{
+    cli::{self, Context},
+    client::Client,
+    fetch::{Fetched, fetch},
+    input::input_path,
+    output::Format,
+    solution::Part,
+};
+
+use common::{StubServer, TempDir};
+
+#[test]
+fn test_fetch_downloads_then_caches() {
+    let server = StubServer::start(|request| match request.path.as_str() {
+        "/2025/day/3/input" => (200, "987654321111111\n".to_string()),
+        _ => (404, "Not Found".to_string()),
+    });
+    let client = Client::new(&server.url, "secret");
+    let dir = TempDir::new("fetch-cache");
+
+    let path = input_path(dir.path(), 3);
+
+    assert_eq!(
+        fetch(&client, dir.path(), 3).unwrap(),
+        Fetched::Downloaded(path.clone())
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "987654321111111\n");
+
+    assert_eq!(
+        fetch(&client, dir.path(), 3).unwrap(),
+        Fetched::Cached(path)
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+    assert!(requests[0].header("user-agent").is_some());
+}
+
+#[test]
+fn test_fetch_errors_leave_nothing_cached() {
+    let server = StubServer::start(|request| match request.path.as_str() {
+        "/2025/day/1/input" => (400, "Puzzle inputs differ by user.".to_string()),
+        _ => (404, "Not Found".to_string()),
+    });
+    let client = Client::new(&server.url, "expired");
+    let dir = TempDir::new("fetch-errors");
+
+    let error = fetch(&client, dir.path(), 1).unwrap_err().to_string();
+    assert!(
+        error.ends_with("session token was rejected (400)"),
+        "{}",
+        error
+    );
+
+    let error = fetch(&client, dir.path(), 12).unwrap_err().to_string();
+    assert!(error.ends_with("day 12 is not available yet"), "{}", error);
+
+    assert!(!input_path(dir.path(), 1).exists());
+    assert!(!input_path(dir.path(), 12).exists());
+}
+
+#[test]
+fn test_fetch_cached_inputs_without_a_session() {
+    let dir = TempDir::new("fetch-no-session");
+    let path = input_path(dir.path(), 3);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "987654321111111\n").unwrap();
+
+    let context = Context {
+        days: vec![3],
+        parts: vec![Part::One, Part::Two],
+        input: None,
+        inputs_dir: dir.path().to_path_buf(),
+        format: Format::Text,
+    };
+
+    cli::fetch::run(&context, Some("http://127.0.0.1:9".to_string()), None).unwrap();
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use std::fs;
+
+use advent_of_code_2025::{
+    client::Client,
+    solution::Part,
+    submit::{Guesses, Verdict, submit},
+};
+
+use common::{StubServer, TempDir};
+
+fn page(message: &str) -> String {
+    format!(
+        "<html><body><main><article><p>{}</p></article></main></body></html>",
+        message
+    )
+}
+
+#[test]
+fn test_submit_records_guesses_and_refuses_ruled_out_answers() {
+    let server = StubServer::start(|request| {
+        let page = match request.body.as_str() {
+            "level=1&answer=500" => page(
+                "That's not the right answer; your answer is too high. Please wait one minute before trying again.",
+            ),
+            "level=1&answer=357" => page("That's the right answer! You are one gold star closer."),
+            _ => return (404, "Not Found".to_string()),
+        };
+
+        (200, page)
+    });
+    let client = Client::new(&server.url, "secret");
+
+    let dir = TempDir::new("guesses");
+    let path = dir.join("guesses.jsonl");
+
+    let mut guesses = Guesses::load(&path).unwrap();
+
+    let response = submit(&client, &mut guesses, 3, Part::One, "500").unwrap();
+    assert_eq!(response.verdict, Verdict::TooHigh);
+
+    let refused = [
+        ("500", "500 was already submitted and was too high"),
+        ("600", "600 is not below 500, which was too high"),
+    ];
+    for (answer, reason) in refused {
+        let error = submit(&client, &mut guesses, 3, Part::One, answer).unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            format!("refusing to submit {}: {}", answer, reason)
+        );
+    }
+
+    // the server asked for a minute's wait, so an answer inside the bounds has to wait too
+    let error = submit(&client, &mut guesses, 3, Part::One, "357").unwrap_err();
+    assert!(
+        format!("{:#}", error).contains("asked to wait another"),
+        "{:#}",
+        error
+    );
+
+    // forget the wait, as if a minute had passed
+    let recorded = fs::read_to_string(&path)
+        .unwrap()
+        .replace("\"wait\":60", "\"wait\":null");
+    fs::write(&path, recorded).unwrap();
+    let mut guesses = Guesses::load(&path).unwrap();
+
+    let response = submit(&client, &mut guesses, 3, Part::One, "357").unwrap();
+    assert_eq!(response.verdict, Verdict::Correct);
+
+    let error = submit(&client, &mut guesses, 3, Part::One, "358").unwrap_err();
+    assert_eq!(
+        format!("{:#}", error),
+        "refusing to submit 358: day 3 part 1 is already solved with 357"
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2025/day/3/answer");
+    assert_eq!(requests[0].header("cookie"), Some("session=secret"));
+    assert_eq!(
+        requests[0].header("content-type"),
+        Some("application/x-www-form-urlencoded")
+    );
+
+    assert_eq!(
+        Guesses::load(&path)
+            .unwrap()
+            .for_part(3, Part::One)
+            .map(|guess| (guess.answer.as_str(), guess.verdict))
+            .collect::<Vec<_>>(),
+        vec![("500", Verdict::TooHigh), ("357", Verdict::Correct)]
+    );
+}
+
+#[test]
+fn test_submit_records_waits() {
+    let server = StubServer::start(|_| {
+        (
+            200,
+            page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 2m 5s left to wait.",
+            ),
+        )
+    });
+    let client = Client::new(&server.url, "secret");
+
+    let dir = TempDir::new("waits");
+    let path = dir.join("guesses.jsonl");
+
+    let mut guesses = Guesses::load(&path).unwrap();
+
+    let response = submit(&client, &mut guesses, 5, Part::Two, "42").unwrap();
+    assert_eq!(response.verdict, Verdict::Wait);
+    assert_eq!(response.wait, Some(std::time::Duration::from_secs(125)));
+
+    // an unjudged answer isn't known to be wrong, but the wait still has to pass
+    let error = submit(&client, &mut guesses, 5, Part::Two, "42").unwrap_err();
+    assert!(
+        format!("{:#}", error).contains("asked to wait another"),
+        "{:#}",
+        error
+    );
+
+    assert_eq!(server.requests().len(), 1);
+}